@@ -101,12 +101,35 @@ pub static LIBLO_ERROR_TEXT_DECODE_FAIL: c_uint = 18;
 /// The library encountered an error that should not have been possible to encounter.
 pub static LIBLO_ERROR_INTERNAL_LOGIC_ERROR: c_uint = 19;
 
+/// A light plugin defines a new record with a FormID outside the object index range reserved for
+/// light plugins.
+#[no_mangle]
+pub static LIBLO_ERROR_INVALID_LIGHT_PLUGIN: c_uint = 20;
+
+/// A medium plugin defines a new record with a FormID outside the object index range reserved for
+/// medium plugins.
+#[no_mangle]
+pub static LIBLO_ERROR_INVALID_MEDIUM_PLUGIN: c_uint = 21;
+
+/// The given plugins have masters that form a circular dependency.
+#[no_mangle]
+pub static LIBLO_ERROR_CIRCULAR_MASTER_DEPENDENCY: c_uint = 22;
+
+/// The given plugins are involved in a cyclic sorting rule interaction.
+#[no_mangle]
+pub static LIBLO_ERROR_CYCLIC_INTERACTION: c_uint = 23;
+
+/// One of the game handle's configured additional plugins directories does not exist or is not a
+/// directory.
+#[no_mangle]
+pub static LIBLO_ERROR_INVALID_ADDITIONAL_PLUGINS_DIRECTORY: c_uint = 24;
+
 /// Matches the value of the highest-numbered return code.
 ///
 /// Provided in case clients wish to incorporate additional return codes in their implementation
 /// and desire some method of avoiding value conflicts.
 #[no_mangle]
-pub static LIBLO_RETURN_MAX: c_uint = 19;
+pub static LIBLO_RETURN_MAX: c_uint = 24;
 
 /// The game handle is using the timestamp-based load order system. Morrowind, Oblivion, Fallout 3
 /// and Fallout: New Vegas all use this system.
@@ -121,6 +144,10 @@ pub static LIBLO_METHOD_TEXTFILE: c_uint = LoadOrderMethod::Textfile as c_uint;
 #[no_mangle]
 pub static LIBLO_METHOD_ASTERISK: c_uint = LoadOrderMethod::Asterisk as c_uint;
 
+/// The game handle is using the OpenMW load order system. OpenMW Morrowind uses this system.
+#[no_mangle]
+pub static LIBLO_METHOD_OPENMW: c_uint = LoadOrderMethod::OpenMW as c_uint;
+
 /// Game code for The Elder Scrolls III: Morrowind.
 #[no_mangle]
 pub static LIBLO_GAME_TES3: c_uint = GameId::Morrowind as c_uint;
@@ -147,4 +174,36 @@ pub static LIBLO_GAME_FO4: c_uint = GameId::Fallout4 as c_uint;
 
 /// Game code for The Elder Scrolls V: Skyrim Special Edition.
 #[no_mangle]
-pub static LIBLO_GAME_TES5SE: c_uint = GameId::SkyrimSE as c_uint;
\ No newline at end of file
+pub static LIBLO_GAME_TES5SE: c_uint = GameId::SkyrimSE as c_uint;
+
+/// Game code for Fallout 4 VR.
+#[no_mangle]
+pub static LIBLO_GAME_FO4VR: c_uint = GameId::Fallout4VR as c_uint;
+
+/// Game code for The Elder Scrolls V: Skyrim VR.
+#[no_mangle]
+pub static LIBLO_GAME_TES5VR: c_uint = GameId::SkyrimVR as c_uint;
+
+/// Game code for Starfield.
+#[no_mangle]
+pub static LIBLO_GAME_STARFIELD: c_uint = GameId::Starfield as c_uint;
+
+/// Game code for OpenMW's variant of The Elder Scrolls III: Morrowind.
+#[no_mangle]
+pub static LIBLO_GAME_OPENMW: c_uint = GameId::OpenMWMorrowind as c_uint;
+
+/// The maximum number of medium plugins that can be active at the same time.
+///
+/// This limit only applies to games that support medium plugins, which is currently only
+/// Starfield. Medium plugins are a distinct plugin class from light plugins, and share their own
+/// load order index with other medium plugins.
+#[no_mangle]
+pub static LIBLO_LIMIT_MAX_ACTIVE_MEDIUM_PLUGINS: c_uint = 256;
+
+/// The maximum number of normal plugins that can be active at the same time, for games that
+/// support medium plugins.
+///
+/// This is lower than the limit that applies to games without medium plugin support, as medium
+/// plugins share the load order index space that would otherwise be available to normal plugins.
+#[no_mangle]
+pub static LIBLO_LIMIT_MAX_ACTIVE_NORMAL_PLUGINS_WITH_MEDIUM_PLUGINS: c_uint = 0xFD;
\ No newline at end of file
@@ -32,6 +32,7 @@ pub enum LoadOrderMethod {
     Timestamp,
     Textfile,
     Asterisk,
+    OpenMW,
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -47,6 +48,7 @@ pub enum GameId {
     Fallout4VR,
     SkyrimVR,
     Starfield,
+    OpenMWMorrowind,
 }
 
 impl GameId {
@@ -62,6 +64,7 @@ impl GameId {
             GameId::Fallout4 => esplugin::GameId::Fallout4,
             GameId::Fallout4VR => esplugin::GameId::Fallout4,
             GameId::Starfield => esplugin::GameId::Starfield,
+            GameId::OpenMWMorrowind => esplugin::GameId::Morrowind,
         }
     }
 
@@ -72,6 +75,10 @@ impl GameId {
             Fallout4 | Fallout4VR | SkyrimSE | SkyrimVR | Starfield
         )
     }
+
+    pub fn supports_medium_plugins(self) -> bool {
+        self == GameId::Starfield
+    }
 }
 
 #[derive(Debug)]
@@ -87,9 +94,14 @@ pub enum Error {
     PluginNotFound(String),
     TooManyActivePlugins {
         light_count: usize,
+        medium_count: usize,
         normal_count: usize,
     },
     DuplicatePlugin(String),
+    CircularMasterDependency(Vec<String>),
+    CyclicInteraction(Vec<String>),
+    InvalidLightPluginFormIds(String),
+    InvalidMediumPluginFormIds(String),
     NonMasterBeforeMaster {
         master: String,
         non_master: String,
@@ -117,6 +129,11 @@ pub enum Error {
     },
     VdfParsingError(PathBuf, String),
     SystemError(i32, OsString),
+    InvalidAdditionalPluginsDirectory(PathBuf),
+    InvalidPluginPosition {
+        index: usize,
+        plugins_len: usize,
+    },
 }
 
 impl From<time::SystemTimeError> for Error {
@@ -156,10 +173,18 @@ impl fmt::Display for Error {
             Error::PluginNotFound(name) => {
                 write!(f, "The plugin \"{name}\" is not in the load order")
             }
-            Error::TooManyActivePlugins {light_count, normal_count } =>
-                write!(f, "Maximum number of active plugins exceeded: there are {normal_count} active normal plugins and {light_count} active light plugins"),
+            Error::TooManyActivePlugins {light_count, medium_count, normal_count } =>
+                write!(f, "Maximum number of active plugins exceeded: there are {normal_count} active normal plugins, {medium_count} active medium plugins and {light_count} active light plugins"),
             Error::DuplicatePlugin(name) =>
                 write!(f, "The given plugin list contains more than one instance of \"{name}\""),
+            Error::CircularMasterDependency(names) =>
+                write!(f, "The plugins {names:?} form a circular master dependency"),
+            Error::CyclicInteraction(names) =>
+                write!(f, "The plugins {names:?} are involved in a cyclic sorting rule interaction"),
+            Error::InvalidLightPluginFormIds(name) =>
+                write!(f, "The light plugin \"{name}\" defines new records outside the object index range reserved for light plugins"),
+            Error::InvalidMediumPluginFormIds(name) =>
+                write!(f, "The medium plugin \"{name}\" defines new records outside the object index range reserved for medium plugins"),
             Error::NonMasterBeforeMaster{ master, non_master} =>
                 write!(f, "Attempted to load the non-master plugin \"{non_master}\" before the master plugin \"{master}\""),
             Error::GameMasterMustLoadFirst(name) =>
@@ -187,6 +212,10 @@ impl fmt::Display for Error {
                 write!(f, "Failed to parse VDF file at {path:?}: {message}"),
             Error::SystemError(code, message) =>
                 write!(f, "Error returned by the operating system, code {code}: {message:?}"),
+            Error::InvalidAdditionalPluginsDirectory(path) =>
+                write!(f, "The additional plugins directory {path:?} does not exist or is not a directory"),
+            Error::InvalidPluginPosition { index, plugins_len } =>
+                write!(f, "The position {index} is out of range for a load order of {plugins_len} plugins"),
         }
     }
 }
@@ -244,6 +273,10 @@ mod tests {
             esplugin::GameId::Starfield,
             GameId::Starfield.to_esplugin_id()
         );
+        assert_eq!(
+            esplugin::GameId::Morrowind,
+            GameId::OpenMWMorrowind.to_esplugin_id()
+        );
     }
 
     #[test]
@@ -258,6 +291,39 @@ mod tests {
         assert!(GameId::Fallout4.supports_light_plugins());
         assert!(GameId::Fallout4VR.supports_light_plugins());
         assert!(GameId::Starfield.supports_light_plugins());
+        assert!(!GameId::OpenMWMorrowind.supports_light_plugins());
+    }
+
+    #[test]
+    fn game_id_supports_medium_plugins_should_be_true_only_for_starfield() {
+        assert!(!GameId::Morrowind.supports_medium_plugins());
+        assert!(!GameId::Oblivion.supports_medium_plugins());
+        assert!(!GameId::Skyrim.supports_medium_plugins());
+        assert!(!GameId::SkyrimSE.supports_medium_plugins());
+        assert!(!GameId::SkyrimVR.supports_medium_plugins());
+        assert!(!GameId::Fallout3.supports_medium_plugins());
+        assert!(!GameId::FalloutNV.supports_medium_plugins());
+        assert!(!GameId::Fallout4.supports_medium_plugins());
+        assert!(!GameId::Fallout4VR.supports_medium_plugins());
+        assert!(GameId::Starfield.supports_medium_plugins());
+        assert!(!GameId::OpenMWMorrowind.supports_medium_plugins());
+    }
+
+    #[test]
+    fn error_display_should_print_all_three_counts_for_too_many_active_plugins() {
+        let string = format!(
+            "{}",
+            Error::TooManyActivePlugins {
+                light_count: 4097,
+                medium_count: 257,
+                normal_count: 256,
+            }
+        );
+
+        assert_eq!(
+            "Maximum number of active plugins exceeded: there are 256 active normal plugins, 257 active medium plugins and 4097 active light plugins",
+            string
+        );
     }
 
     #[test]
@@ -274,6 +340,58 @@ mod tests {
         assert_eq!("Expected a UTF-8 string, got bytes [2F, 47, 03]", string);
     }
 
+    #[test]
+    fn error_display_should_print_circular_master_dependency_plugin_names() {
+        let string = format!(
+            "{}",
+            Error::CircularMasterDependency(vec!["A.esm".to_string(), "B.esm".to_string()])
+        );
+
+        assert_eq!(
+            "The plugins [\"A.esm\", \"B.esm\"] form a circular master dependency",
+            string
+        );
+    }
+
+    #[test]
+    fn error_display_should_print_cyclic_interaction_plugin_names() {
+        let string = format!(
+            "{}",
+            Error::CyclicInteraction(vec!["A.esp".to_string(), "B.esp".to_string()])
+        );
+
+        assert_eq!(
+            "The plugins [\"A.esp\", \"B.esp\"] are involved in a cyclic sorting rule interaction",
+            string
+        );
+    }
+
+    #[test]
+    fn error_display_should_print_invalid_light_plugin_form_ids_plugin_name() {
+        let string = format!(
+            "{}",
+            Error::InvalidLightPluginFormIds("Blank.esl".to_string())
+        );
+
+        assert_eq!(
+            "The light plugin \"Blank.esl\" defines new records outside the object index range reserved for light plugins",
+            string
+        );
+    }
+
+    #[test]
+    fn error_display_should_print_invalid_medium_plugin_form_ids_plugin_name() {
+        let string = format!(
+            "{}",
+            Error::InvalidMediumPluginFormIds("Blank.esm".to_string())
+        );
+
+        assert_eq!(
+            "The medium plugin \"Blank.esm\" defines new records outside the object index range reserved for medium plugins",
+            string
+        );
+    }
+
     #[test]
     fn error_display_should_print_os_string_as_quoted_string() {
         let string = format!("{}", Error::SystemError(1, OsString::from("foo")));
@@ -283,4 +401,33 @@ mod tests {
             string
         );
     }
+
+    #[test]
+    fn error_display_should_print_invalid_additional_plugins_directory_path() {
+        let string = format!(
+            "{}",
+            Error::InvalidAdditionalPluginsDirectory(PathBuf::from("foo"))
+        );
+
+        assert_eq!(
+            "The additional plugins directory \"foo\" does not exist or is not a directory",
+            string
+        );
+    }
+
+    #[test]
+    fn error_display_should_print_invalid_plugin_position_index_and_plugins_len() {
+        let string = format!(
+            "{}",
+            Error::InvalidPluginPosition {
+                index: 5,
+                plugins_len: 3,
+            }
+        );
+
+        assert_eq!(
+            "The position 5 is out of range for a load order of 3 plugins",
+            string
+        );
+    }
 }
@@ -18,10 +18,12 @@
  */
 
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::read_dir;
 use std::mem;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use encoding_rs::WINDOWS_1252;
 use rayon::prelude::*;
@@ -33,6 +35,394 @@ use crate::game_settings::GameSettings;
 use crate::plugin::{has_plugin_extension, trim_dot_ghost, Plugin};
 use crate::GameId;
 
+/// A single rule violation found by [`MutableLoadOrder::validate_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadOrderProblem {
+    /// `non_master` loads before the master file `master`, which does not
+    /// list it as one of its masters.
+    NonMasterBeforeMaster { master: String, non_master: String },
+    /// `plugin` is a non-master that `master` lists as one of its masters,
+    /// but it loads after `master`.
+    UnrepresentedHoist { plugin: String, master: String },
+    /// The named light plugin defines new (non-overriding) records outside
+    /// the object-index window the game reserves for light plugins, so it
+    /// would corrupt other plugins' records if loaded as light.
+    InvalidLightPluginFormIds(String),
+    /// `plugin` is active and has `master` as one of its masters, but
+    /// `master` isn't installed.
+    MissingMaster { plugin: String, master: String },
+    /// `plugin` is active and has `master` as one of its masters, but
+    /// `master` doesn't load before it.
+    MasterLoadsAfterDependent { plugin: String, master: String },
+    /// `plugin` is active and has `master` as one of its masters, but
+    /// `master` isn't active.
+    InactiveMaster { plugin: String, master: String },
+}
+
+/// The maximum number of active full-size (non-light, non-medium) plugins
+/// the engine supports: they occupy one of the 255 regular load order
+/// slots each.
+const MAX_ACTIVE_NORMAL_PLUGINS: usize = 255;
+
+/// The maximum number of active full-size plugins a game that also
+/// supports medium plugins allows: medium plugins claim the `0xFD` load
+/// order slot for themselves, so normal plugins are limited to `0x00`-`0xFC`.
+const MAX_ACTIVE_NORMAL_PLUGINS_WITH_MEDIUM_PLUGINS: usize = 0xFD;
+
+/// The maximum number of active medium plugins the engine supports: they
+/// share a single regular load order slot (`0xFD`) between them.
+const MAX_ACTIVE_MEDIUM_PLUGINS: usize = 256;
+
+/// The maximum number of active light plugins the engine supports: they
+/// share a single regular load order slot (0xFE) between them.
+const MAX_ACTIVE_LIGHT_PLUGINS: usize = 4096;
+
+/// Returns the inclusive object-index window that a light plugin's new
+/// (non-overriding) records must fall within for `game`, or `None` if
+/// `game` doesn't support light plugins.
+fn light_plugin_object_index_range(game: GameId) -> Option<RangeInclusive<u32>> {
+    match game {
+        GameId::SkyrimSE | GameId::SkyrimVR => Some(0x800..=0xFFF),
+        GameId::Fallout4 | GameId::Fallout4VR => Some(0x001..=0xFFF),
+        GameId::Starfield => Some(0x000..=0xFFF),
+        _ => None,
+    }
+}
+
+/// Returns the inclusive object-index window that a medium plugin's new
+/// (non-overriding) records must fall within for `game`, or `None` if
+/// `game` doesn't support medium plugins.
+fn medium_plugin_object_index_range(game: GameId) -> Option<RangeInclusive<u32>> {
+    match game {
+        GameId::Starfield => Some(0x0000..=0xFFFF),
+        _ => None,
+    }
+}
+
+/// Checks whether `plugin`'s new (non-overriding) records all fall within
+/// `range`. Records it overrides from one of its masters are not subject
+/// to the restriction, since they don't need a new object index of their
+/// own.
+fn has_valid_new_record_object_indices(
+    plugin: &Plugin,
+    range: &RangeInclusive<u32>,
+) -> Result<bool, Error> {
+    let master_count = plugin.masters()?.len();
+
+    let valid = plugin.form_ids()?.iter().all(|&raw_form_id| {
+        let mod_index = (raw_form_id >> 24) as usize;
+        let object_index = raw_form_id & 0x00FF_FFFF;
+
+        mod_index < master_count || range.contains(&object_index)
+    });
+
+    Ok(valid)
+}
+
+/// Checks whether `plugin`, if it's a light plugin, only defines new
+/// records within the object-index window `game` reserves for light
+/// plugins' own records. Records it overrides from one of its masters are
+/// not subject to the restriction, since they don't need a new object
+/// index of their own.
+fn is_light_plugin_valid(plugin: &Plugin, game: GameId) -> Result<bool, Error> {
+    if !plugin.is_light_plugin() {
+        return Ok(true);
+    }
+
+    let Some(range) = light_plugin_object_index_range(game) else {
+        return Ok(true);
+    };
+
+    has_valid_new_record_object_indices(plugin, &range)
+}
+
+/// Checks whether `plugin`, if it's a medium plugin, only defines new
+/// records within the object-index window `game` reserves for medium
+/// plugins' own records. Records it overrides from one of its masters are
+/// not subject to the restriction, since they don't need a new object
+/// index of their own.
+fn is_medium_plugin_valid(plugin: &Plugin, game: GameId) -> Result<bool, Error> {
+    if !plugin.is_medium_plugin() {
+        return Ok(true);
+    }
+
+    let Some(range) = medium_plugin_object_index_range(game) else {
+        return Ok(true);
+    };
+
+    has_valid_new_record_object_indices(plugin, &range)
+}
+
+/// Errors with [`Error::InvalidLightPluginFormIds`] naming the first light
+/// plugin in `plugins` whose new records fall outside the object-index
+/// window `game` allows for light plugins.
+pub fn validate_light_plugins(plugins: &[Plugin], game: GameId) -> Result<(), Error> {
+    for plugin in plugins {
+        if !is_light_plugin_valid(plugin, game)? {
+            return Err(Error::InvalidLightPluginFormIds(plugin.name().to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Errors with [`Error::InvalidMediumPluginFormIds`] naming the first
+/// medium plugin in `plugins` whose new records fall outside the
+/// object-index window `game` allows for medium plugins.
+pub fn validate_medium_plugins(plugins: &[Plugin], game: GameId) -> Result<(), Error> {
+    for plugin in plugins {
+        if !is_medium_plugin_valid(plugin, game)? {
+            return Err(Error::InvalidMediumPluginFormIds(plugin.name().to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Errors with [`Error::TooManyActivePlugins`] if more active plugins are
+/// active than `game` supports, counting light and (if `game` supports
+/// them) medium plugins towards their own, separate limits since they
+/// each share a single regular load order slot between them.
+pub fn validate_active_plugin_counts(plugins: &[Plugin], game: GameId) -> Result<(), Error> {
+    let (light_count, medium_count, normal_count) = plugins.iter().filter(|p| p.is_active()).fold(
+        (0usize, 0usize, 0usize),
+        |(light_count, medium_count, normal_count), p| {
+            if p.is_light_plugin() {
+                (light_count + 1, medium_count, normal_count)
+            } else if p.is_medium_plugin() {
+                (light_count, medium_count + 1, normal_count)
+            } else {
+                (light_count, medium_count, normal_count + 1)
+            }
+        },
+    );
+
+    let max_normal_count = if game.supports_medium_plugins() {
+        MAX_ACTIVE_NORMAL_PLUGINS_WITH_MEDIUM_PLUGINS
+    } else {
+        MAX_ACTIVE_NORMAL_PLUGINS
+    };
+    let max_medium_count = if game.supports_medium_plugins() {
+        MAX_ACTIVE_MEDIUM_PLUGINS
+    } else {
+        0
+    };
+
+    if normal_count > max_normal_count
+        || medium_count > max_medium_count
+        || light_count > MAX_ACTIVE_LIGHT_PLUGINS
+    {
+        Err(Error::TooManyActivePlugins {
+            light_count,
+            medium_count,
+            normal_count,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct DirectoryStamp {
+    modified: Option<SystemTime>,
+    entry_count: usize,
+}
+
+fn stamp_directory(directory: &Path) -> DirectoryStamp {
+    let entries = read_dir(directory);
+
+    DirectoryStamp {
+        modified: std::fs::metadata(directory).and_then(|m| m.modified()).ok(),
+        entry_count: entries.map_or(0, Iterator::count),
+    }
+}
+
+/// Caches the result of scanning one or more plugin directories, and avoids
+/// repeating the scan and sort while none of the directories have changed.
+#[derive(Clone, Debug, Default)]
+pub struct PluginScanner {
+    cache: Option<(Vec<DirectoryStamp>, Vec<String>)>,
+}
+
+impl PluginScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discards any cached scan result, forcing the next call to `scan` to
+    /// re-read the given directories.
+    pub fn invalidate(&mut self) {
+        self.cache = None;
+    }
+
+    /// Equivalent to calling `invalidate` followed by `scan`.
+    pub fn refresh(&mut self, directories: &[PathBuf], game: GameId) -> Vec<String> {
+        self.invalidate();
+        self.scan(directories, game)
+    }
+
+    /// Returns the plugin filenames found in `directories`, in the same
+    /// order and with the same deduplication behaviour as
+    /// [`find_plugins_in_dirs`]. The directories are only re-read and
+    /// re-sorted if their modification time or entry count has changed
+    /// since the last call.
+    pub fn scan(&mut self, directories: &[PathBuf], game: GameId) -> Vec<String> {
+        let stamps: Vec<_> = directories.iter().map(|d| stamp_directory(d)).collect();
+
+        if let Some((cached_stamps, cached_names)) = &self.cache {
+            if *cached_stamps == stamps {
+                return cached_names.clone();
+            }
+        }
+
+        let names = find_plugins_in_dirs(directories, game);
+        self.cache = Some((stamps, names.clone()));
+
+        names
+    }
+}
+
+/// Describes how [`MutableLoadOrder::merge_with_disk`] reconciled the
+/// in-memory load order against what was found on disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Plugins that were present on disk but not in memory, and so were
+    /// inserted.
+    pub added: Vec<String>,
+    /// Plugins that were present in memory but not on disk, and so were
+    /// dropped.
+    pub removed: Vec<String>,
+    /// Plugins that are present both in memory and on disk, but whose
+    /// position changed as a result of the merge.
+    pub moved: Vec<String>,
+}
+
+/// A user-supplied ordering constraint for [`sort_plugins_with_rules`].
+/// Plugin names are resolved case-insensitively, ignoring any `.ghost`
+/// suffix, the same way plugin names are matched everywhere else.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SortRule {
+    /// The first plugin must load before the second.
+    Order(String, String),
+    /// The plugin should load as close to the start of the load order as
+    /// the other rules and the master/non-master invariant allow.
+    NearStart(String),
+    /// The plugin should load as close to the end of the load order as
+    /// the other rules and the master/non-master invariant allow.
+    NearEnd(String),
+    /// The first plugin requires the second, so the second must load
+    /// before it.
+    Requires(String, String),
+}
+
+fn add_edge(from: usize, to: usize, successors: &mut [Vec<usize>], in_degree: &mut [usize]) {
+    if from != to {
+        successors[from].push(to);
+        in_degree[to] += 1;
+    }
+}
+
+/// Performs a Kahn's algorithm topological sort of `plugins` like
+/// `topological_sort_by_masters`, but additionally takes hard edges from
+/// `rules` (an `Order` rule adds an edge from its first plugin to its
+/// second, a `Requires` rule adds an edge from its second plugin to its
+/// first, a `NearStart`/`NearEnd` rule instead biases the
+/// tie-break so that unconstrained plugin towards the start/end of the
+/// order). Unknown plugin names in `rules` are ignored.
+pub fn sort_plugins_with_rules(plugins: &[Plugin], rules: &[SortRule]) -> Result<Vec<usize>, Error> {
+    let name_indices: HashMap<UniCase<String>, usize> = plugins
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (UniCase::new(trim_dot_ghost(p.name()).to_string()), i))
+        .collect();
+    let resolve = |name: &str| {
+        name_indices
+            .get(&UniCase::new(trim_dot_ghost(name).to_string()))
+            .copied()
+    };
+
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+    let mut in_degree: Vec<usize> = vec![0; plugins.len()];
+
+    for (index, plugin) in plugins.iter().enumerate() {
+        for master in plugin.masters()? {
+            if let Some(master_index) = resolve(&master) {
+                add_edge(master_index, index, &mut successors, &mut in_degree);
+            }
+        }
+    }
+
+    let mut near_start = HashSet::new();
+    let mut near_end = HashSet::new();
+
+    for rule in rules {
+        match rule {
+            SortRule::Order(first, second) | SortRule::Requires(second, first) => {
+                if let (Some(first_index), Some(second_index)) = (resolve(first), resolve(second))
+                {
+                    add_edge(first_index, second_index, &mut successors, &mut in_degree);
+                }
+            }
+            SortRule::NearStart(name) => {
+                if let Some(index) = resolve(name) {
+                    near_start.insert(index);
+                }
+            }
+            SortRule::NearEnd(name) => {
+                if let Some(index) = resolve(name) {
+                    near_end.insert(index);
+                }
+            }
+        }
+    }
+
+    let priority = |i: usize| {
+        let master_tier = u8::from(!plugins[i].is_master_file());
+        let position_tier = if near_start.contains(&i) {
+            0u8
+        } else if near_end.contains(&i) {
+            2u8
+        } else {
+            1u8
+        };
+        (master_tier, position_tier, i)
+    };
+
+    let mut ready: BTreeSet<(u8, u8, usize)> = (0..plugins.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(priority)
+        .collect();
+
+    let mut order = Vec::with_capacity(plugins.len());
+    let mut emitted = vec![false; plugins.len()];
+
+    while let Some(&key) = ready.iter().next() {
+        ready.remove(&key);
+        let node = key.2;
+
+        order.push(node);
+        emitted[node] = true;
+
+        for &successor in &successors[node] {
+            in_degree[successor] -= 1;
+            if in_degree[successor] == 0 {
+                ready.insert(priority(successor));
+            }
+        }
+    }
+
+    if order.len() < plugins.len() {
+        let remaining_plugins = (0..plugins.len())
+            .filter(|&i| !emitted[i])
+            .map(|i| plugins[i].name().to_string())
+            .collect();
+
+        return Err(Error::CyclicInteraction(remaining_plugins));
+    }
+
+    Ok(order)
+}
+
 pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
     fn plugins_mut(&mut self) -> &mut Vec<Plugin>;
 
@@ -52,6 +442,35 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         find_plugins_in_dirs(&directories, self.game_settings().id())
     }
 
+    /// Errors with [`Error::InvalidAdditionalPluginsDirectory`] naming the
+    /// first of the game's configured additional plugins directories that
+    /// doesn't exist or isn't a directory. Store-specific installs (e.g.
+    /// Microsoft Store Fallout 4) can have DLC plugins outside the main
+    /// plugins directory, and this catches a misconfigured path before it
+    /// silently drops those plugins from [`find_plugins`](Self::find_plugins).
+    fn validate_additional_plugins_directories(&self) -> Result<(), Error> {
+        for directory in self.game_settings().additional_plugins_directories() {
+            if !directory.is_dir() {
+                return Err(Error::InvalidAdditionalPluginsDirectory(directory.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// As `find_plugins`, but also returns the names of any plugins that
+    /// were skipped because they differ from an already-found plugin only
+    /// by case.
+    fn find_plugins_with_warnings(&self) -> (Vec<String>, Vec<String>) {
+        let mut directories = self
+            .game_settings()
+            .additional_plugins_directories()
+            .to_vec();
+        directories.push(self.game_settings().plugins_directory());
+
+        find_plugins_in_dirs_with_warnings(&directories, self.game_settings().id())
+    }
+
     fn validate_index(&self, plugin: &Plugin, index: usize) -> Result<(), Error> {
         if plugin.is_master_file() {
             validate_master_file_index(self.plugins(), plugin, index)
@@ -60,6 +479,84 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         }
     }
 
+    /// Walks the whole load order and returns every master/non-master
+    /// ordering violation found, instead of stopping at the first one, as
+    /// well as every light plugin whose new records have FormIDs outside
+    /// the range reserved for light plugins.
+    fn validate_all(&self) -> Result<Vec<LoadOrderProblem>, Error> {
+        let plugins = self.plugins();
+        let game = self.game_settings().id();
+        let mut problems = Vec::new();
+
+        for (index, plugin) in plugins.iter().enumerate() {
+            if plugin.is_master_file() {
+                collect_master_file_problems(plugins, plugin, index, &mut problems)?;
+            } else {
+                collect_non_master_file_problems(plugins, plugin, index, &mut problems)?;
+            }
+
+            if !is_light_plugin_valid(plugin, game)? {
+                problems.push(LoadOrderProblem::InvalidLightPluginFormIds(
+                    plugin.name().to_string(),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Parses every active plugin's master list and checks that each of
+    /// its masters is installed, loads before it, and is itself active.
+    /// Unlike [`validate_all`](Self::validate_all), which checks the
+    /// master/non-master ordering invariants and light plugin FormIDs, this is a
+    /// deeper, explicitly opt-in check for callers that want to know
+    /// upfront whether the current load order is actually loadable by the
+    /// game, and are willing to pay the cost of parsing every active
+    /// plugin's master list to find out.
+    fn validate_active_plugin_masters(&self) -> Result<Vec<LoadOrderProblem>, Error> {
+        let plugins = self.plugins();
+        let name_indices: HashMap<UniCase<String>, usize> = plugins
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (UniCase::new(trim_dot_ghost(p.name()).to_string()), i))
+            .collect();
+
+        let mut problems = Vec::new();
+
+        for (index, plugin) in plugins.iter().enumerate() {
+            if !self.is_active(plugin.name()) {
+                continue;
+            }
+
+            for master in plugin.masters()? {
+                let key = UniCase::new(trim_dot_ghost(&master).to_string());
+                match name_indices.get(&key) {
+                    None => problems.push(LoadOrderProblem::MissingMaster {
+                        plugin: plugin.name().to_string(),
+                        master,
+                    }),
+                    Some(&master_index) => {
+                        if master_index >= index {
+                            problems.push(LoadOrderProblem::MasterLoadsAfterDependent {
+                                plugin: plugin.name().to_string(),
+                                master: master.clone(),
+                            });
+                        }
+
+                        if !self.is_active(plugins[master_index].name()) {
+                            problems.push(LoadOrderProblem::InactiveMaster {
+                                plugin: plugin.name().to_string(),
+                                master,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
     fn lookup_plugins(&mut self, active_plugin_names: &[&str]) -> Result<Vec<usize>, Error> {
         active_plugin_names
             .par_iter()
@@ -77,7 +574,8 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         plugin_name: &str,
         position: usize,
     ) -> Result<usize, Error> {
-        if let Some(x) = self.index_of(plugin_name) {
+        let source_index = self.index_of(plugin_name);
+        if let Some(x) = source_index {
             if x == position {
                 return Ok(position);
             }
@@ -85,6 +583,16 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
 
         let plugin = get_plugin_to_insert_at(self, plugin_name, position)?;
 
+        // If the plugin was already present before `position`, removing it
+        // in `get_plugin_to_insert_at` shifted everything from `position`
+        // onwards (including the reference plugin this index was taken
+        // from) one slot to the left, so the target position needs the
+        // same adjustment to still refer to the same slot.
+        let position = match source_index {
+            Some(x) if x < position => position - 1,
+            _ => position,
+        };
+
         if position >= self.plugins().len() {
             self.plugins_mut().push(plugin);
             Ok(self.plugins().len() - 1)
@@ -94,6 +602,50 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         }
     }
 
+    fn move_plugin_before(
+        &mut self,
+        plugin_name: &str,
+        reference_plugin_name: &str,
+    ) -> Result<usize, Error> {
+        let reference_index = self
+            .index_of(reference_plugin_name)
+            .ok_or_else(|| Error::PluginNotFound(reference_plugin_name.to_string()))?;
+
+        self.move_or_insert_plugin_with_index(plugin_name, reference_index)
+    }
+
+    fn move_plugin_after(
+        &mut self,
+        plugin_name: &str,
+        reference_plugin_name: &str,
+    ) -> Result<usize, Error> {
+        let reference_index = self
+            .index_of(reference_plugin_name)
+            .ok_or_else(|| Error::PluginNotFound(reference_plugin_name.to_string()))?;
+
+        self.move_or_insert_plugin_with_index(plugin_name, reference_index + 1)
+    }
+
+    /// Loads `plugin_name` if it is not already present, and places it
+    /// immediately before `reference_plugin_name`.
+    fn insert_plugin_before(
+        &mut self,
+        plugin_name: &str,
+        reference_plugin_name: &str,
+    ) -> Result<usize, Error> {
+        self.move_plugin_before(plugin_name, reference_plugin_name)
+    }
+
+    /// Loads `plugin_name` if it is not already present, and places it
+    /// immediately after `reference_plugin_name`.
+    fn insert_plugin_after(
+        &mut self,
+        plugin_name: &str,
+        reference_plugin_name: &str,
+    ) -> Result<usize, Error> {
+        self.move_plugin_after(plugin_name, reference_plugin_name)
+    }
+
     fn deactivate_all(&mut self) {
         for plugin in self.plugins_mut() {
             plugin.deactivate();
@@ -122,6 +674,29 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         plugin_name_tuples: Vec<(String, bool)>,
         installed_filenames: Vec<String>,
     ) {
+        // On a case-sensitive filesystem (e.g. under Proton/Wine),
+        // loadorder.txt and plugins.txt may record a plugin's name with
+        // different casing to the file actually installed on disk, so
+        // resolve each entry to the on-disk casing before constructing its
+        // Plugin. This is unnecessary (and a wasted directory scan) on a
+        // case-insensitive filesystem, so it's configurable per game.
+        let plugin_name_tuples: Vec<(String, bool)> =
+            if self.game_settings().use_case_insensitive_plugin_resolution() {
+                let canonical_names = canonical_name_map(&installed_filenames);
+
+                plugin_name_tuples
+                    .into_iter()
+                    .map(|(name, active)| {
+                        let canonical_name = canonical_names
+                            .get(&UniCase::new(trim_dot_ghost(&name).to_string()))
+                            .cloned();
+                        (canonical_name.unwrap_or(name), active)
+                    })
+                    .collect()
+            } else {
+                plugin_name_tuples
+            };
+
         let plugins: Vec<_> = remove_duplicates_icase(plugin_name_tuples, installed_filenames)
             .into_par_iter()
             .filter_map(|(filename, active)| {
@@ -134,6 +709,188 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         }
     }
 
+    /// Resolves `name` to the casing actually used by the file on disk, if
+    /// a plugin with that name (ignoring case and any `.ghost` suffix) is
+    /// installed and `GameSettings::use_case_insensitive_plugin_resolution`
+    /// is enabled.
+    fn canonicalize_plugin_name(&self, name: &str) -> Option<String> {
+        if !self.game_settings().use_case_insensitive_plugin_resolution() {
+            return None;
+        }
+
+        canonical_name_map(&self.find_plugins())
+            .get(&UniCase::new(trim_dot_ghost(name).to_string()))
+            .cloned()
+    }
+
+    /// Returns the in-game mod index an active light plugin is assigned:
+    /// its position among the other active light plugins, all of which
+    /// share the single regular load order slot the game gives to light
+    /// plugins. This is distinct from `index_of`, which returns the
+    /// plugin's ordinary load-order position. Returns `None` if the named
+    /// plugin isn't installed, isn't active, or isn't a light plugin.
+    fn light_plugin_mod_index(&self, plugin_name: &str) -> Option<usize> {
+        let index = self.index_of(plugin_name)?;
+        let plugin = &self.plugins()[index];
+
+        if !plugin.is_light_plugin() || !self.is_active(plugin_name) {
+            return None;
+        }
+
+        self.plugins()
+            .iter()
+            .filter(|p| p.is_light_plugin() && self.is_active(p.name()))
+            .position(|p| p.name_matches(plugin_name))
+    }
+
+    /// Produces a stable, minimal-change reordering of the plugins that
+    /// respects all master/non-master and master-dependency relationships,
+    /// without discarding or adding any plugin.
+    fn sort(&mut self) -> Result<(), Error> {
+        let order = topological_sort_by_masters(self.plugins())?;
+
+        let mut sorted_plugins = Vec::with_capacity(order.len());
+        for index in order {
+            sorted_plugins.push(self.plugins()[index].clone());
+        }
+
+        *self.plugins_mut() = sorted_plugins;
+
+        Ok(())
+    }
+
+    /// Atomically repositions several plugins at once, as a single
+    /// transactional reorder: `positions` maps each plugin's current index
+    /// to its new index, and is applied using the same index-correcting
+    /// logic as `hoist_masters`. Every index in `positions` must be within
+    /// range for the current load order, or this returns
+    /// `Error::InvalidPluginPosition` without changing anything. The
+    /// resulting arrangement is also validated as a whole before it is
+    /// committed, so a batch of moves that would transiently violate
+    /// master/non-master ordering does not leave the load order in an
+    /// inconsistent state.
+    fn set_plugin_positions(&mut self, positions: BTreeMap<usize, usize>) -> Result<(), Error> {
+        let plugins_len = self.plugins().len();
+        if let Some(&index) = positions
+            .iter()
+            .flat_map(|(from, to)| [from, to])
+            .find(|&&index| index >= plugins_len)
+        {
+            return Err(Error::InvalidPluginPosition { index, plugins_len });
+        }
+
+        let mut new_plugins = self.plugins().to_vec();
+
+        move_elements(&mut new_plugins, positions);
+
+        validate_load_order(&new_plugins)?;
+
+        *self.plugins_mut() = new_plugins;
+
+        Ok(())
+    }
+
+    /// Reconciles the in-memory load order with `disk_plugin_names`, an
+    /// ordered plugin list freshly read from disk, without discarding
+    /// pending in-memory edits: plugins present in both keep their
+    /// relative in-memory order, plugins that are new on disk are
+    /// inserted next to whichever of their on-disk neighbours is already
+    /// placed (falling back to the usual master/non-master hoisting rules
+    /// if neither neighbour can be found), and plugins no longer on disk
+    /// are dropped. The merge is rolled back if the result would not be a
+    /// valid load order.
+    fn merge_with_disk(&mut self, disk_plugin_names: &[String]) -> Result<MergeReport, Error> {
+        let old_plugins = self.plugins().to_vec();
+
+        let disk_set: HashSet<UniCase<String>> = disk_plugin_names
+            .iter()
+            .map(|n| UniCase::new(trim_dot_ghost(n).to_string()))
+            .collect();
+
+        let removed: Vec<String> = old_plugins
+            .iter()
+            .filter(|p| !disk_set.contains(&UniCase::new(trim_dot_ghost(p.name()).to_string())))
+            .map(|p| p.name().to_string())
+            .collect();
+
+        let retained: Vec<Plugin> = old_plugins
+            .iter()
+            .filter(|p| disk_set.contains(&UniCase::new(trim_dot_ghost(p.name()).to_string())))
+            .cloned()
+            .collect();
+
+        let retained_set: HashSet<UniCase<String>> = retained
+            .iter()
+            .map(|p| UniCase::new(trim_dot_ghost(p.name()).to_string()))
+            .collect();
+
+        *self.plugins_mut() = retained;
+
+        let mut added = Vec::new();
+        for (disk_index, disk_name) in disk_plugin_names.iter().enumerate() {
+            let key = UniCase::new(trim_dot_ghost(disk_name).to_string());
+            if retained_set.contains(&key) {
+                continue;
+            }
+
+            if let Ok(plugin) = Plugin::new(disk_name, self.game_settings()) {
+                // Slot the new plugin in next to whichever of its on-disk
+                // neighbours is already placed, so it lands at its on-disk
+                // position relative to the plugins around it instead of
+                // always being hoisted to the nearest master/non-master
+                // boundary.
+                let position = disk_plugin_names[..disk_index]
+                    .iter()
+                    .rev()
+                    .find_map(|name| self.index_of(name))
+                    .map(|index| index + 1)
+                    .or_else(|| {
+                        disk_plugin_names[disk_index + 1..]
+                            .iter()
+                            .find_map(|name| self.index_of(name))
+                    });
+
+                match position {
+                    Some(position) => self.plugins_mut().insert(position, plugin),
+                    None => {
+                        insert(self, plugin);
+                    }
+                }
+                added.push(disk_name.clone());
+            }
+        }
+
+        if let Err(error) = validate_load_order(self.plugins()) {
+            *self.plugins_mut() = old_plugins;
+            return Err(error);
+        }
+
+        let old_retained_indices: HashMap<UniCase<String>, usize> = old_plugins
+            .iter()
+            .filter(|p| retained_set.contains(&UniCase::new(trim_dot_ghost(p.name()).to_string())))
+            .enumerate()
+            .map(|(index, p)| (UniCase::new(trim_dot_ghost(p.name()).to_string()), index))
+            .collect();
+
+        let moved = self
+            .plugins()
+            .iter()
+            .filter(|p| retained_set.contains(&UniCase::new(trim_dot_ghost(p.name()).to_string())))
+            .enumerate()
+            .filter_map(|(new_index, p)| {
+                let key = UniCase::new(trim_dot_ghost(p.name()).to_string());
+                let old_index = *old_retained_indices.get(&key)?;
+                (old_index != new_index).then(|| p.name().to_string())
+            })
+            .collect();
+
+        Ok(MergeReport {
+            added,
+            removed,
+            moved,
+        })
+    }
+
     fn add_implicitly_active_plugins(&mut self) -> Result<(), Error> {
         let plugin_names = self.game_settings().implicitly_active_plugins().to_vec();
 
@@ -230,56 +987,274 @@ pub fn hoist_masters(plugins: &mut Vec<Plugin>) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn generic_insert_position(plugins: &[Plugin], plugin: &Plugin) -> Option<usize> {
-    if plugin.is_master_file() {
-        find_first_non_master_position(plugins)
-    } else {
-        // Check that there isn't a master that would hoist this plugin.
-        plugins.iter().filter(|p| p.is_master_file()).position(|p| {
-            p.masters()
-                .map(|masters| masters.iter().any(|m| plugin.name_matches(m)))
-                .unwrap_or(false)
-        })
-    }
-}
-
-fn find_plugins_in_dirs(directories: &[PathBuf], game: GameId) -> Vec<String> {
-    let mut dir_entries: Vec<_> = directories
+/// Performs a Kahn's algorithm topological sort of `plugins`, with a hard
+/// edge from each master to every plugin that has it as a master. Among
+/// plugins that are currently ready to be emitted, master files are
+/// preferred over non-masters, and lower original indices are preferred
+/// over higher ones, so that the existing order is disturbed as little as
+/// possible.
+fn topological_sort_by_masters(plugins: &[Plugin]) -> Result<Vec<usize>, Error> {
+    let name_indices: HashMap<UniCase<String>, usize> = plugins
         .iter()
-        .flat_map(read_dir)
-        .flatten()
-        .filter_map(Result::ok)
-        .filter(|e| e.file_type().map(|f| f.is_file()).unwrap_or(false))
-        .filter(|e| {
-            e.file_name()
-                .to_str()
-                .map(|f| has_plugin_extension(f, game))
-                .unwrap_or(false)
-        })
+        .enumerate()
+        .map(|(i, p)| (UniCase::new(trim_dot_ghost(p.name()).to_string()), i))
         .collect();
 
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+    let mut in_degree: Vec<usize> = vec![0; plugins.len()];
+
+    for (index, plugin) in plugins.iter().enumerate() {
+        for master in plugin.masters()? {
+            if let Some(&master_index) =
+                name_indices.get(&UniCase::new(trim_dot_ghost(&master).to_string()))
+            {
+                if master_index != index {
+                    successors[master_index].push(index);
+                    in_degree[index] += 1;
+                }
+            }
+        }
+    }
+
+    let priority = |i: usize| (u8::from(!plugins[i].is_master_file()), i);
+
+    let mut ready: BTreeSet<(u8, usize)> = (0..plugins.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(priority)
+        .collect();
+
+    let mut order = Vec::with_capacity(plugins.len());
+    let mut emitted = vec![false; plugins.len()];
+
+    while let Some(&key) = ready.iter().next() {
+        ready.remove(&key);
+        let node = key.1;
+
+        order.push(node);
+        emitted[node] = true;
+
+        for &successor in &successors[node] {
+            in_degree[successor] -= 1;
+            if in_degree[successor] == 0 {
+                ready.insert(priority(successor));
+            }
+        }
+    }
+
+    if order.len() < plugins.len() {
+        let remaining_plugins = (0..plugins.len())
+            .filter(|&i| !emitted[i])
+            .map(|i| plugins[i].name().to_string())
+            .collect();
+
+        return Err(Error::CircularMasterDependency(remaining_plugins));
+    }
+
+    Ok(order)
+}
+
+pub fn generic_insert_position(plugins: &[Plugin], plugin: &Plugin) -> Option<usize> {
+    if plugin.is_master_file() {
+        find_first_non_master_position(plugins)
+    } else {
+        // Check that there isn't a master that would hoist this plugin.
+        plugins.iter().filter(|p| p.is_master_file()).position(|p| {
+            p.masters()
+                .map(|masters| masters.iter().any(|m| plugin.name_matches(m)))
+                .unwrap_or(false)
+        })
+    }
+}
+
+fn find_plugins_in_dirs(directories: &[PathBuf], game: GameId) -> Vec<String> {
+    find_plugins_in_dirs_with_warnings(directories, game).0
+}
+
+/// Scans `directories` for plugins in the same way as `find_plugins_in_dirs`,
+/// but also returns the names of any plugins that were skipped because they
+/// differ from an already-found plugin only by case (which can happen on a
+/// case-sensitive filesystem). The plugin that sorts first under the
+/// existing timestamp/filename tiebreak is kept.
+fn find_plugins_in_dirs_with_warnings(
+    directories: &[PathBuf],
+    game: GameId,
+) -> (Vec<String>, Vec<String>) {
+    let mut entries: Vec<(String, Option<SystemTime>)> = Vec::new();
+
+    for directory in directories {
+        let Ok(read_dir_iter) = read_dir(directory) else {
+            continue;
+        };
+
+        entries.reserve(read_dir_iter.size_hint().0);
+
+        for entry in read_dir_iter.filter_map(Result::ok) {
+            if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                continue;
+            }
+
+            let Some(filename) = entry.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+
+            if !has_plugin_extension(&filename, game) {
+                continue;
+            }
+
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            entries.push((filename, modified));
+        }
+    }
+
     // Sort by file modification timestamps, in ascending order. If two timestamps are equal, sort
     // by filenames (in ascending order for Starfield, descending otherwise).
-    dir_entries.sort_unstable_by(|e1, e2| {
-        let m1 = e1.metadata().and_then(|m| m.modified()).ok();
-        let m2 = e2.metadata().and_then(|m| m.modified()).ok();
+    entries.sort_by(|(n1, m1), (n2, m2)| match m1.cmp(m2) {
+        Ordering::Equal if game == GameId::Starfield => n1.cmp(n2),
+        Ordering::Equal => n1.cmp(n2).reverse(),
+        x => x,
+    });
 
-        match m1.cmp(&m2) {
-            Ordering::Equal if game == GameId::Starfield => e1.file_name().cmp(&e2.file_name()),
-            Ordering::Equal => e1.file_name().cmp(&e2.file_name()).reverse(),
-            x => x,
+    // Case-variant duplicates are deduplicated after sorting, not during the
+    // directory walk: `read_dir`'s iteration order is unspecified, so
+    // resolving the duplicate against raw walk order would make which file
+    // wins dependent on filesystem/OS behaviour instead of on the
+    // timestamp/filename tiebreak above.
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    let mut names = Vec::new();
+    let mut scratch = String::new();
+
+    for (filename, _) in entries {
+        scratch.clear();
+        scratch.push_str(trim_dot_ghost(&filename));
+
+        if seen.insert(UniCase::new(mem::take(&mut scratch))) {
+            names.push(filename);
+        } else {
+            duplicates.push(filename);
         }
-    });
+    }
 
-    let mut set = HashSet::new();
+    (names, duplicates)
+}
 
-    dir_entries
-        .into_iter()
-        .filter_map(|e| e.file_name().to_str().map(str::to_owned))
-        .filter(|filename| set.insert(UniCase::new(trim_dot_ghost(filename).to_string())))
+/// Builds a map from case-folded, ghost-suffix-trimmed plugin name to the
+/// authoritative on-disk filename, so that names can be resolved to the
+/// casing actually used by the filesystem.
+fn canonical_name_map(filenames: &[String]) -> HashMap<UniCase<String>, String> {
+    filenames
+        .iter()
+        .map(|f| (UniCase::new(trim_dot_ghost(f).to_string()), f.clone()))
         .collect()
 }
 
+fn owning_plugin_key(name: &str) -> UniCase<String> {
+    UniCase::new(trim_dot_ghost(name).to_string())
+}
+
+/// Builds the set of `(owning plugin, object index)` pairs that identify
+/// the records `plugin` touches. Each of the plugin's raw 32-bit FormIDs
+/// splits into a `mod_index` (the high byte) and an `object_index` (the
+/// low three bytes); `mod_index` is resolved against the plugin's ordered
+/// master list with the plugin itself appended as the final entry, so a
+/// `mod_index` less than the number of masters identifies an override of
+/// a master's record, while `mod_index == masters.len()` identifies a new
+/// record defined by the plugin itself.
+fn plugin_record_keys(plugin: &Plugin) -> Result<HashSet<(UniCase<String>, u32)>, Error> {
+    let mut masters = plugin.masters()?;
+    masters.push(plugin.name().to_string());
+
+    let keys = plugin
+        .form_ids()?
+        .iter()
+        .map(|&raw_form_id| {
+            let mod_index = (raw_form_id >> 24) as usize;
+            let object_index = raw_form_id & 0x00FF_FFFF;
+            let owner = masters.get(mod_index).map_or(plugin.name(), String::as_str);
+
+            (owning_plugin_key(owner), object_index)
+        })
+        .collect();
+
+    Ok(keys)
+}
+
+/// Reports which pairs of `plugins` touch at least one of the same record,
+/// with the later-loading plugin of each pair listed second, as it's the
+/// one that wins the conflict. `plugins` is assumed to be in load order.
+pub fn find_conflicting_plugins(plugins: &[Plugin]) -> Result<Vec<(String, String)>, Error> {
+    let record_keys = plugins
+        .iter()
+        .map(plugin_record_keys)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut conflicts = Vec::new();
+    for earlier in 0..plugins.len() {
+        for later in (earlier + 1)..plugins.len() {
+            if !record_keys[earlier].is_disjoint(&record_keys[later]) {
+                conflicts.push((
+                    plugins[earlier].name().to_string(),
+                    plugins[later].name().to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Counts how many records `plugin_a` and `plugin_b` both touch.
+pub fn count_overlapping_records(plugin_a: &Plugin, plugin_b: &Plugin) -> Result<usize, Error> {
+    let keys_a = plugin_record_keys(plugin_a)?;
+    let keys_b = plugin_record_keys(plugin_b)?;
+
+    Ok(keys_a.intersection(&keys_b).count())
+}
+
+/// Per-plugin report of record-level overrides with the other plugins it's
+/// loaded alongside: the other plugins it overrides at least one record of,
+/// and the other, later-loading plugins that override at least one of its
+/// own records instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PluginRecordConflicts {
+    pub overrides: HashSet<String>,
+    pub overridden_by: HashSet<String>,
+}
+
+/// For each of `plugins` (assumed to be in load order), reports which other
+/// plugins' records it overrides and which other plugins override its own
+/// records. Unlike [`find_conflicting_plugins`], which only reports that a
+/// pair of plugins touches the same record somewhere, this walks records in
+/// load order and attributes each one to the plugin that actually wins it,
+/// so a record touched by three or more plugins is credited to the pair
+/// that actually conflicts over it rather than every plugin that happens to
+/// touch it.
+pub fn find_record_overrides(plugins: &[Plugin]) -> Result<Vec<PluginRecordConflicts>, Error> {
+    let record_keys = plugins
+        .iter()
+        .map(plugin_record_keys)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut last_owner: HashMap<(UniCase<String>, u32), usize> = HashMap::new();
+    let mut conflicts = vec![PluginRecordConflicts::default(); plugins.len()];
+
+    for (index, keys) in record_keys.iter().enumerate() {
+        for key in keys {
+            if let Some(&previous_index) = last_owner.get(key) {
+                conflicts[index]
+                    .overrides
+                    .insert(plugins[previous_index].name().to_string());
+                conflicts[previous_index]
+                    .overridden_by
+                    .insert(plugins[index].name().to_string());
+            }
+            last_owner.insert(key.clone(), index);
+        }
+    }
+
+    Ok(conflicts)
+}
+
 fn to_plugin(
     plugin_name: &str,
     existing_plugins: &[Plugin],
@@ -377,6 +1352,85 @@ fn validate_non_master_file_index(
     }
 }
 
+fn collect_master_file_problems(
+    plugins: &[Plugin],
+    plugin: &Plugin,
+    index: usize,
+    problems: &mut Vec<LoadOrderProblem>,
+) -> Result<(), Error> {
+    let preceding_plugins = if index < plugins.len() {
+        &plugins[..index]
+    } else {
+        plugins
+    };
+
+    let previous_master_pos = preceding_plugins
+        .iter()
+        .rposition(|p| p.is_master_file())
+        .unwrap_or(0);
+
+    let masters = plugin.masters()?;
+    let master_names: HashSet<_> = masters.iter().map(|m| UniCase::new(m.as_str())).collect();
+
+    for p in preceding_plugins.iter().skip(previous_master_pos + 1) {
+        if !master_names.contains(&UniCase::new(p.name())) {
+            problems.push(LoadOrderProblem::NonMasterBeforeMaster {
+                master: plugin.name().to_string(),
+                non_master: p.name().to_string(),
+            });
+        }
+    }
+
+    for p in plugins
+        .iter()
+        .skip(index)
+        .filter(|p| !p.is_master_file())
+        .filter(|p| master_names.contains(&UniCase::new(p.name())))
+    {
+        problems.push(LoadOrderProblem::UnrepresentedHoist {
+            plugin: p.name().to_string(),
+            master: plugin.name().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn collect_non_master_file_problems(
+    plugins: &[Plugin],
+    plugin: &Plugin,
+    index: usize,
+    problems: &mut Vec<LoadOrderProblem>,
+) -> Result<(), Error> {
+    for master_file in plugins.iter().take(index).filter(|p| p.is_master_file()) {
+        if master_file
+            .masters()?
+            .iter()
+            .any(|m| plugin.name_matches(m))
+        {
+            problems.push(LoadOrderProblem::UnrepresentedHoist {
+                plugin: plugin.name().to_string(),
+                master: master_file.name().to_string(),
+            });
+        }
+    }
+
+    if let Some(next_master) = plugins.iter().skip(index).find(|p| p.is_master_file()) {
+        if !next_master
+            .masters()?
+            .iter()
+            .any(|m| plugin.name_matches(m))
+        {
+            problems.push(LoadOrderProblem::NonMasterBeforeMaster {
+                master: next_master.name().to_string(),
+                non_master: plugin.name().to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 fn map_to_plugins<T: ReadableLoadOrderBase + Sync + ?Sized>(
     load_order: &T,
     plugin_names: &[&str],
@@ -448,7 +1502,7 @@ fn are_plugin_names_unique(plugin_names: &[&str]) -> bool {
     unique_plugin_names.len() == plugin_names.len()
 }
 
-fn validate_load_order(plugins: &[Plugin]) -> Result<(), Error> {
+pub fn validate_load_order(plugins: &[Plugin]) -> Result<(), Error> {
     let first_non_master_pos = match find_first_non_master_position(plugins) {
         None => return Ok(()),
         Some(x) => x,
@@ -634,6 +1688,19 @@ mod tests {
         load_order
     }
 
+    fn prepare_light_plugin_load_order(game_path: &Path) -> TestLoadOrder {
+        let settings = prepare(game_path);
+        copy_to_test_dir("Blank.esl", "Blank.esl", &settings);
+
+        TestLoadOrder {
+            plugins: vec![
+                Plugin::new(settings.master_file(), &settings).unwrap(),
+                Plugin::with_active("Blank.esl", &settings, true).unwrap(),
+            ],
+            game_settings: settings,
+        }
+    }
+
     fn prepare_plugins(game_path: &Path, blank_esp_source: &str) -> Vec<Plugin> {
         let settings = game_settings_for_test(GameId::SkyrimSE, game_path);
 
@@ -754,6 +1821,75 @@ mod tests {
         assert!(load_order.validate_index(&plugin, 2).is_err());
     }
 
+    #[test]
+    fn move_plugin_before_should_place_plugin_immediately_before_reference_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let position = load_order
+            .move_plugin_before("Blank.esp", "Blank - Different.esp")
+            .unwrap();
+
+        assert_eq!(
+            position + 1,
+            load_order.index_of("Blank - Different.esp").unwrap()
+        );
+    }
+
+    #[test]
+    fn move_plugin_before_should_place_plugin_immediately_before_reference_plugin_when_it_was_already_before_it(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let mut load_order = TestLoadOrder {
+            plugins: vec![
+                Plugin::new(settings.master_file(), &settings).unwrap(),
+                Plugin::new("Blank.esm", &settings).unwrap(),
+                Plugin::new("Blank.esp", &settings).unwrap(),
+                Plugin::new("Blank - Different.esp", &settings).unwrap(),
+            ],
+            game_settings: settings,
+        };
+
+        assert!(
+            load_order.index_of("Blank.esp").unwrap()
+                < load_order.index_of("Blank - Different.esp").unwrap()
+        );
+
+        let position = load_order
+            .move_plugin_before("Blank.esp", "Blank - Different.esp")
+            .unwrap();
+
+        assert_eq!(position, load_order.index_of("Blank.esp").unwrap());
+        assert_eq!(
+            position + 1,
+            load_order.index_of("Blank - Different.esp").unwrap()
+        );
+    }
+
+    #[test]
+    fn move_plugin_after_should_place_plugin_immediately_after_reference_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let position = load_order
+            .move_plugin_after("Blank - Different.esp", "Blank.esp")
+            .unwrap();
+
+        assert_eq!(position, load_order.index_of("Blank.esp").unwrap() + 1);
+    }
+
+    #[test]
+    fn move_plugin_before_should_error_if_the_reference_plugin_is_not_found() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        assert!(load_order
+            .move_plugin_before("Blank.esp", "missing.esp")
+            .is_err());
+    }
+
     #[test]
     fn find_plugins_in_dirs_should_sort_files_by_modification_timestamp() {
         let tmp_dir = tempdir().unwrap();
@@ -862,90 +1998,533 @@ mod tests {
     }
 
     #[test]
-    fn move_elements_should_correct_later_indices_to_account_for_earlier_moves() {
-        let mut vec = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
-        let mut from_to_indices = BTreeMap::new();
-        from_to_indices.insert(6, 3);
-        from_to_indices.insert(5, 2);
-        from_to_indices.insert(7, 1);
+    fn sort_should_leave_an_already_valid_load_order_unchanged() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
 
-        move_elements(&mut vec, from_to_indices);
+        let plugin_names_before: Vec<_> =
+            load_order.plugins().iter().map(Plugin::name).collect();
 
-        assert_eq!(vec![0, 7, 1, 5, 2, 6, 3, 4, 8], vec);
+        load_order.sort().unwrap();
+
+        let plugin_names_after: Vec<_> =
+            load_order.plugins().iter().map(Plugin::name).collect();
+
+        assert_eq!(plugin_names_before, plugin_names_after);
     }
 
     #[test]
-    fn validate_load_order_should_be_ok_if_there_are_only_master_files() {
+    fn sort_should_move_a_non_master_before_a_master_that_depends_on_it() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let mut load_order = prepare_hoisted_load_order(&tmp_dir.path());
 
-        let plugins = vec![
-            Plugin::new(settings.master_file(), &settings).unwrap(),
-            Plugin::new("Blank.esm", &settings).unwrap(),
-        ];
+        let plugin = Plugin::new("Blank - Different.esm", load_order.game_settings()).unwrap();
+        load_order.plugins.push(plugin);
+        let plugin = Plugin::new(
+            "Blank - Different Master Dependent.esm",
+            load_order.game_settings(),
+        )
+        .unwrap();
+        load_order.plugins.push(plugin);
 
-        assert!(validate_load_order(&plugins).is_ok());
+        load_order.sort().unwrap();
+
+        let non_master_pos = load_order.index_of("Blank - Different.esm").unwrap();
+        let master_pos = load_order
+            .index_of("Blank - Different Master Dependent.esm")
+            .unwrap();
+
+        assert!(non_master_pos < master_pos);
     }
 
     #[test]
-    fn validate_load_order_should_be_ok_if_there_are_no_master_files() {
+    fn sort_plugins_with_rules_should_order_plugins_according_to_an_order_rule() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let load_order = prepare_load_order(&tmp_dir.path());
 
-        let plugins = vec![
-            Plugin::new("Blank.esp", &settings).unwrap(),
-            Plugin::new("Blank - Different.esp", &settings).unwrap(),
-        ];
+        let rules = [SortRule::Order(
+            "Blank - Different.esp".to_string(),
+            "Blank.esp".to_string(),
+        )];
 
-        assert!(validate_load_order(&plugins).is_ok());
+        let order = sort_plugins_with_rules(load_order.plugins(), &rules).unwrap();
+        let sorted_names: Vec<_> = order.iter().map(|&i| load_order.plugins()[i].name()).collect();
+
+        let first_pos = sorted_names.iter().position(|&n| n == "Blank - Different.esp").unwrap();
+        let second_pos = sorted_names.iter().position(|&n| n == "Blank.esp").unwrap();
+
+        assert!(first_pos < second_pos);
     }
 
     #[test]
-    fn validate_load_order_should_be_ok_if_master_files_are_before_all_others() {
+    fn sort_plugins_with_rules_should_move_a_near_start_plugin_towards_the_start() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let load_order = prepare_load_order(&tmp_dir.path());
 
-        let plugins = vec![
-            Plugin::new("Blank.esm", &settings).unwrap(),
-            Plugin::new("Blank.esp", &settings).unwrap(),
-        ];
+        let rules = [SortRule::NearStart(
+            "Blank - Different.esp".to_string(),
+        )];
 
-        assert!(validate_load_order(&plugins).is_ok());
+        let order = sort_plugins_with_rules(load_order.plugins(), &rules).unwrap();
+        let first_non_master_plugin = order
+            .iter()
+            .map(|&i| &load_order.plugins()[i])
+            .find(|p| !p.is_master_file())
+            .unwrap();
+
+        assert_eq!("Blank - Different.esp", first_non_master_plugin.name());
     }
 
     #[test]
-    fn validate_load_order_should_be_ok_if_hoisted_non_masters_load_before_masters() {
+    fn sort_plugins_with_rules_should_keep_master_files_before_non_masters() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let load_order = prepare_load_order(&tmp_dir.path());
 
-        let plugins = vec![
-            Plugin::new("Blank.esm", &settings).unwrap(),
-            Plugin::new("Blank.esp", &settings).unwrap(),
-            Plugin::new("Blank - Plugin Dependent.esm", &settings).unwrap(),
-        ];
+        let master_file = load_order.game_settings().master_file();
+        let rules = [SortRule::NearEnd(master_file.to_string())];
 
-        assert!(validate_load_order(&plugins).is_ok());
+        let order = sort_plugins_with_rules(load_order.plugins(), &rules).unwrap();
+
+        let master_pos = order
+            .iter()
+            .position(|&i| load_order.plugins()[i].name() == master_file)
+            .unwrap();
+        let non_master_pos = order
+            .iter()
+            .position(|&i| load_order.plugins()[i].name() == "Blank.esp")
+            .unwrap();
+
+        assert!(master_pos < non_master_pos);
     }
 
     #[test]
-    fn validate_load_order_should_error_if_non_masters_are_hoisted_earlier_than_needed() {
+    fn sort_plugins_with_rules_should_error_if_the_rules_form_a_cycle() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let load_order = prepare_load_order(&tmp_dir.path());
 
-        let plugins = vec![
-            Plugin::new("Blank.esp", &settings).unwrap(),
-            Plugin::new("Blank.esm", &settings).unwrap(),
-            Plugin::new("Blank - Plugin Dependent.esm", &settings).unwrap(),
+        let rules = [
+            SortRule::Order("Blank.esp".to_string(), "Blank - Different.esp".to_string()),
+            SortRule::Order("Blank - Different.esp".to_string(), "Blank.esp".to_string()),
         ];
 
-        assert!(validate_load_order(&plugins).is_err());
+        match sort_plugins_with_rules(load_order.plugins(), &rules).unwrap_err() {
+            Error::CyclicInteraction(names) => {
+                assert_eq!(2, names.len());
+                assert!(names.contains(&"Blank.esp".to_string()));
+                assert!(names.contains(&"Blank - Different.esp".to_string()));
+            }
+            e => panic!("Expected a cyclic interaction error, got {e:?}"),
+        }
     }
 
     #[test]
-    fn validate_load_order_should_error_if_master_files_load_before_non_masters_they_have_as_masters(
-    ) {
+    fn canonicalize_plugin_name_should_return_the_on_disk_casing_for_a_case_insensitive_match() {
         let tmp_dir = tempdir().unwrap();
-        let settings = prepare(&tmp_dir.path());
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        assert_eq!(
+            Some("Blank.esp".to_string()),
+            load_order.canonicalize_plugin_name("BLANK.ESP")
+        );
+    }
+
+    #[test]
+    fn canonicalize_plugin_name_should_return_none_if_no_installed_plugin_matches() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        assert_eq!(None, load_order.canonicalize_plugin_name("missing.esp"));
+    }
+
+    #[test]
+    fn plugin_scanner_should_return_the_same_result_as_an_uncached_scan() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        let directories = [load_order.game_settings.plugins_directory()];
+        let game = load_order.game_settings.id();
+
+        let mut scanner = PluginScanner::new();
+        let result = scanner.scan(&directories, game);
+
+        assert_eq!(find_plugins_in_dirs(&directories, game), result);
+    }
+
+    #[test]
+    fn plugin_scanner_should_reuse_the_cached_result_while_the_directory_is_unchanged() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        let directories = [load_order.game_settings.plugins_directory()];
+        let game = load_order.game_settings.id();
+
+        let mut scanner = PluginScanner::new();
+        let first = scanner.scan(&directories, game);
+
+        // Changing a file's own mtime doesn't change its parent directory's
+        // mtime or entry count, so the cached result should still be used
+        // even though re-scanning would give a different sort order.
+        filetime::set_file_mtime(
+            load_order
+                .game_settings
+                .plugins_directory()
+                .join("Blank.esp"),
+            filetime::FileTime::zero(),
+        )
+        .unwrap();
+
+        let second = scanner.scan(&directories, game);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn plugin_scanner_should_rescan_after_a_plugin_is_added_to_the_directory() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        let directories = [load_order.game_settings.plugins_directory()];
+        let game = load_order.game_settings.id();
+
+        let mut scanner = PluginScanner::new();
+        let first = scanner.scan(&directories, game);
+
+        copy_to_test_dir("Blank.esp", "New.esp", &load_order.game_settings);
+
+        let second = scanner.scan(&directories, game);
+
+        assert_ne!(first, second);
+        assert!(second.iter().any(|n| n == "New.esp"));
+    }
+
+    #[test]
+    fn find_plugins_in_dirs_with_warnings_should_report_case_variant_duplicates() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        copy_to_test_dir(
+            "Blank.esp",
+            "BLANK.ESP",
+            &load_order.game_settings,
+        );
+
+        let (names, duplicates) = find_plugins_in_dirs_with_warnings(
+            &[load_order.game_settings.plugins_directory()],
+            load_order.game_settings.id(),
+        );
+
+        assert_eq!(
+            1,
+            names
+                .iter()
+                .filter(|n| UniCase::new(n.as_str()) == UniCase::new("Blank.esp"))
+                .count()
+        );
+        assert_eq!(1, duplicates.len());
+    }
+
+    #[test]
+    fn find_plugins_in_dirs_with_warnings_should_keep_the_case_variant_that_sorts_first_by_mtime() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        copy_to_test_dir("Blank.esp", "BLANK.ESP", &load_order.game_settings);
+
+        let plugins_directory = load_order.game_settings.plugins_directory();
+        // Entries are sorted by ascending mtime, so the earlier mtime is
+        // what determines the survivor, not directory walk order.
+        filetime::set_file_mtime(
+            plugins_directory.join("Blank.esp"),
+            filetime::FileTime::from_unix_time(1321009991, 0),
+        )
+        .unwrap();
+        filetime::set_file_mtime(
+            plugins_directory.join("BLANK.ESP"),
+            filetime::FileTime::from_unix_time(1321010051, 0),
+        )
+        .unwrap();
+
+        let (names, duplicates) = find_plugins_in_dirs_with_warnings(
+            &[plugins_directory],
+            load_order.game_settings.id(),
+        );
+
+        assert!(names.iter().any(|n| n == "Blank.esp"));
+        assert_eq!(vec!["BLANK.ESP".to_string()], duplicates);
+    }
+
+    #[test]
+    fn set_plugin_positions_should_apply_all_moves_atomically() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let esp_index = load_order.index_of("Blank.esp").unwrap();
+        let different_esp_index = load_order.index_of("Blank - Different.esp").unwrap();
+
+        let mut positions = BTreeMap::new();
+        positions.insert(esp_index, different_esp_index);
+
+        assert!(load_order.set_plugin_positions(positions).is_ok());
+        assert!(load_order.index_of("Blank.esp").unwrap() >= different_esp_index);
+    }
+
+    #[test]
+    fn set_plugin_positions_should_not_change_anything_if_the_result_would_be_invalid() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+
+        let master_index = load_order
+            .index_of(load_order.game_settings.master_file())
+            .unwrap();
+        let last_index = load_order.plugins().len() - 1;
+
+        let mut positions = BTreeMap::new();
+        positions.insert(master_index, last_index);
+
+        assert!(load_order.set_plugin_positions(positions).is_err());
+
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn set_plugin_positions_should_error_instead_of_panicking_on_an_out_of_range_index() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let out_of_range_index = load_order.plugins().len();
+
+        let mut positions = BTreeMap::new();
+        positions.insert(0, out_of_range_index);
+
+        let error = load_order.set_plugin_positions(positions).unwrap_err();
+
+        match error {
+            Error::InvalidPluginPosition { index, plugins_len } => {
+                assert_eq!(out_of_range_index, index);
+                assert_eq!(existing_filenames.len(), plugins_len);
+            }
+            _ => panic!("expected Error::InvalidPluginPosition, got {error:?}"),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn merge_with_disk_should_drop_plugins_no_longer_present_on_disk() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        let disk_names: Vec<String> = load_order
+            .plugins()
+            .iter()
+            .filter(|p| p.name() != "Blank.esp")
+            .map(|p| p.name().to_string())
+            .collect();
+
+        let report = load_order.merge_with_disk(&disk_names).unwrap();
+
+        assert_eq!(vec!["Blank.esp".to_string()], report.removed);
+        assert!(load_order.index_of("Blank.esp").is_none());
+    }
+
+    #[test]
+    fn merge_with_disk_should_insert_plugins_newly_present_on_disk() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        copy_to_test_dir(
+            "Blank - Different.esp",
+            "New.esp",
+            &load_order.game_settings,
+        );
+
+        let mut disk_names: Vec<String> = load_order
+            .plugins()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect();
+        disk_names.push("New.esp".to_string());
+
+        let report = load_order.merge_with_disk(&disk_names).unwrap();
+
+        assert_eq!(vec!["New.esp".to_string()], report.added);
+        assert!(load_order.index_of("New.esp").is_some());
+    }
+
+    #[test]
+    fn merge_with_disk_should_insert_a_new_plugin_at_its_on_disk_position() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        copy_to_test_dir(
+            "Blank - Different.esp",
+            "New.esp",
+            &load_order.game_settings,
+        );
+
+        let mut disk_names: Vec<String> = load_order
+            .plugins()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect();
+        let insert_at = disk_names.len() - 1;
+        disk_names.insert(insert_at, "New.esp".to_string());
+
+        let report = load_order.merge_with_disk(&disk_names).unwrap();
+
+        assert_eq!(vec!["New.esp".to_string()], report.added);
+        assert_eq!(Some(insert_at), load_order.index_of("New.esp"));
+    }
+
+    #[test]
+    fn merge_with_disk_should_not_report_retained_plugins_as_moved_just_because_a_plugin_was_added(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_load_order(&tmp_dir.path());
+
+        copy_to_test_dir(
+            "Blank - Different.esp",
+            "New.esp",
+            &load_order.game_settings,
+        );
+
+        let mut disk_names: Vec<String> = load_order
+            .plugins()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect();
+        disk_names.insert(1, "New.esp".to_string());
+
+        let report = load_order.merge_with_disk(&disk_names).unwrap();
+
+        assert_eq!(vec!["New.esp".to_string()], report.added);
+        assert!(report.moved.is_empty());
+    }
+
+    #[test]
+    fn move_elements_should_correct_later_indices_to_account_for_earlier_moves() {
+        let mut vec = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let mut from_to_indices = BTreeMap::new();
+        from_to_indices.insert(6, 3);
+        from_to_indices.insert(5, 2);
+        from_to_indices.insert(7, 1);
+
+        move_elements(&mut vec, from_to_indices);
+
+        assert_eq!(vec![0, 7, 1, 5, 2, 6, 3, 4, 8], vec);
+    }
+
+    #[test]
+    fn validate_all_should_return_an_empty_vec_for_a_valid_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        assert_eq!(Vec::<LoadOrderProblem>::new(), load_order.validate_all().unwrap());
+    }
+
+    #[test]
+    fn validate_all_should_collect_every_violation_instead_of_stopping_at_the_first() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new(settings.master_file(), &settings).unwrap(),
+            Plugin::new("Blank.esp", &settings).unwrap(),
+            Plugin::new("Blank - Different.esp", &settings).unwrap(),
+            Plugin::new("Blank.esm", &settings).unwrap(),
+        ];
+
+        let load_order = FixedOrder {
+            game_settings: settings,
+            plugins,
+        };
+
+        let problems = load_order.validate_all().unwrap();
+
+        assert!(problems.contains(&LoadOrderProblem::NonMasterBeforeMaster {
+            master: "Blank.esm".to_string(),
+            non_master: "Blank.esp".to_string(),
+        }));
+        assert!(problems.contains(&LoadOrderProblem::NonMasterBeforeMaster {
+            master: "Blank.esm".to_string(),
+            non_master: "Blank - Different.esp".to_string(),
+        }));
+    }
+
+    #[test]
+    fn validate_load_order_should_be_ok_if_there_are_only_master_files() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new(settings.master_file(), &settings).unwrap(),
+            Plugin::new("Blank.esm", &settings).unwrap(),
+        ];
+
+        assert!(validate_load_order(&plugins).is_ok());
+    }
+
+    #[test]
+    fn validate_load_order_should_be_ok_if_there_are_no_master_files() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new("Blank.esp", &settings).unwrap(),
+            Plugin::new("Blank - Different.esp", &settings).unwrap(),
+        ];
+
+        assert!(validate_load_order(&plugins).is_ok());
+    }
+
+    #[test]
+    fn validate_load_order_should_be_ok_if_master_files_are_before_all_others() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new("Blank.esm", &settings).unwrap(),
+            Plugin::new("Blank.esp", &settings).unwrap(),
+        ];
+
+        assert!(validate_load_order(&plugins).is_ok());
+    }
+
+    #[test]
+    fn validate_load_order_should_be_ok_if_hoisted_non_masters_load_before_masters() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new("Blank.esm", &settings).unwrap(),
+            Plugin::new("Blank.esp", &settings).unwrap(),
+            Plugin::new("Blank - Plugin Dependent.esm", &settings).unwrap(),
+        ];
+
+        assert!(validate_load_order(&plugins).is_ok());
+    }
+
+    #[test]
+    fn validate_load_order_should_error_if_non_masters_are_hoisted_earlier_than_needed() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::new("Blank.esp", &settings).unwrap(),
+            Plugin::new("Blank.esm", &settings).unwrap(),
+            Plugin::new("Blank - Plugin Dependent.esm", &settings).unwrap(),
+        ];
+
+        assert!(validate_load_order(&plugins).is_err());
+    }
+
+    #[test]
+    fn validate_load_order_should_error_if_master_files_load_before_non_masters_they_have_as_masters(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
 
         let plugins = vec![
             Plugin::new("Blank.esm", &settings).unwrap(),
@@ -973,4 +2552,176 @@ mod tests {
         let first_non_master = super::find_first_non_master_position(&plugins);
         assert_eq!(1, first_non_master.unwrap());
     }
+
+    #[test]
+    fn validate_additional_plugins_directories_should_be_ok_if_none_are_configured() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_load_order(&tmp_dir.path());
+
+        assert!(load_order.validate_additional_plugins_directories().is_ok());
+    }
+
+    #[test]
+    fn validate_light_plugins_should_be_ok_if_no_light_plugin_has_out_of_range_new_records() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert!(
+            validate_light_plugins(load_order.plugins(), GameId::SkyrimSE).is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_medium_plugins_should_be_ok_if_no_plugin_is_a_medium_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert!(validate_medium_plugins(load_order.plugins(), GameId::Starfield).is_ok());
+    }
+
+    #[test]
+    fn validate_active_plugin_counts_should_be_ok_if_within_the_normal_and_light_limits() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert!(validate_active_plugin_counts(load_order.plugins(), GameId::SkyrimSE).is_ok());
+    }
+
+    #[test]
+    fn validate_active_plugin_counts_should_be_ok_for_a_game_that_supports_medium_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert!(validate_active_plugin_counts(load_order.plugins(), GameId::Starfield).is_ok());
+    }
+
+    #[test]
+    fn light_plugin_mod_index_should_be_none_for_a_full_size_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert_eq!(
+            None,
+            load_order.light_plugin_mod_index(load_order.game_settings().master_file())
+        );
+    }
+
+    #[test]
+    fn light_plugin_mod_index_should_be_zero_for_the_first_active_light_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_light_plugin_load_order(&tmp_dir.path());
+
+        assert_eq!(Some(0), load_order.light_plugin_mod_index("Blank.esl"));
+    }
+
+    struct FixedOrder {
+        game_settings: GameSettings,
+        plugins: Vec<Plugin>,
+    }
+
+    impl ReadableLoadOrderBase for FixedOrder {
+        fn game_settings_base(&self) -> &GameSettings {
+            &self.game_settings
+        }
+
+        fn plugins(&self) -> &[Plugin] {
+            &self.plugins
+        }
+    }
+
+    impl MutableLoadOrder for FixedOrder {
+        fn plugins_mut(&mut self) -> &mut Vec<Plugin> {
+            &mut self.plugins
+        }
+
+        fn insert_position(&self, plugin: &Plugin) -> Option<usize> {
+            generic_insert_position(self.plugins(), plugin)
+        }
+    }
+
+    #[test]
+    fn validate_active_plugin_masters_should_return_an_empty_vec_if_all_masters_are_present_earlier_and_active(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::with_active("Blank.esm", &settings, true).unwrap(),
+            Plugin::with_active("Blank.esp", &settings, true).unwrap(),
+            Plugin::with_active("Blank - Plugin Dependent.esm", &settings, true).unwrap(),
+        ];
+
+        let load_order = FixedOrder { game_settings: settings, plugins };
+
+        assert_eq!(
+            Vec::<LoadOrderProblem>::new(),
+            load_order.validate_active_plugin_masters().unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_active_plugin_masters_should_report_a_missing_master_of_an_active_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::with_active("Blank.esm", &settings, true).unwrap(),
+            Plugin::with_active("Blank - Plugin Dependent.esm", &settings, true).unwrap(),
+        ];
+
+        let load_order = FixedOrder { game_settings: settings, plugins };
+
+        let problems = load_order.validate_active_plugin_masters().unwrap();
+
+        assert_eq!(
+            vec![LoadOrderProblem::MissingMaster {
+                plugin: "Blank - Plugin Dependent.esm".to_string(),
+                master: "Blank.esp".to_string(),
+            }],
+            problems
+        );
+    }
+
+    #[test]
+    fn validate_active_plugin_masters_should_report_a_master_that_loads_after_its_dependent() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::with_active("Blank.esm", &settings, true).unwrap(),
+            Plugin::with_active("Blank - Plugin Dependent.esm", &settings, true).unwrap(),
+            Plugin::with_active("Blank.esp", &settings, true).unwrap(),
+        ];
+
+        let load_order = FixedOrder { game_settings: settings, plugins };
+
+        let problems = load_order.validate_active_plugin_masters().unwrap();
+
+        assert!(problems.contains(&LoadOrderProblem::MasterLoadsAfterDependent {
+            plugin: "Blank - Plugin Dependent.esm".to_string(),
+            master: "Blank.esp".to_string(),
+        }));
+    }
+
+    #[test]
+    fn validate_active_plugin_masters_should_report_an_inactive_master_of_an_active_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = prepare(&tmp_dir.path());
+
+        let plugins = vec![
+            Plugin::with_active("Blank.esm", &settings, true).unwrap(),
+            Plugin::with_active("Blank.esp", &settings, false).unwrap(),
+            Plugin::with_active("Blank - Plugin Dependent.esm", &settings, true).unwrap(),
+        ];
+
+        let load_order = FixedOrder { game_settings: settings, plugins };
+
+        assert_eq!(
+            vec![LoadOrderProblem::InactiveMaster {
+                plugin: "Blank - Plugin Dependent.esm".to_string(),
+                master: "Blank.esp".to_string(),
+            }],
+            load_order.validate_active_plugin_masters().unwrap()
+        );
+    }
 }
@@ -0,0 +1,351 @@
+/*
+ * This file is part of libloadorder
+ *
+ * Copyright (C) 2017 Oliver Hamlet
+ *
+ * libloadorder is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * libloadorder is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
+ */
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use unicase::UniCase;
+
+use super::mutable::{generic_insert_position, MutableLoadOrder};
+use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
+use super::writable::{
+    activate, add, create_parent_dirs, deactivate, remove, set_active_plugins, WritableLoadOrder,
+};
+use crate::enums::Error;
+use crate::game_settings::GameSettings;
+use crate::plugin::Plugin;
+
+/// A single line read from an openmw.cfg file. `Data` and `Content` are the
+/// two keys this backend manages; every other line (comments, blank lines,
+/// and any other key such as `fallback-archive`) is kept as `Other` so that
+/// rewriting the file never loses settings this crate doesn't understand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ConfigLine {
+    Data(String),
+    Content(String),
+    Other(String),
+}
+
+fn parse_config_line(line: &str) -> ConfigLine {
+    if let Some(value) = line.strip_prefix("data=") {
+        ConfigLine::Data(value.trim().to_string())
+    } else if let Some(value) = line.strip_prefix("content=") {
+        ConfigLine::Content(value.trim().to_string())
+    } else {
+        ConfigLine::Other(line.to_string())
+    }
+}
+
+fn config_line_to_string(line: &ConfigLine) -> String {
+    match line {
+        ConfigLine::Data(value) => format!("data={value}"),
+        ConfigLine::Content(value) => format!("content={value}"),
+        ConfigLine::Other(line) => line.clone(),
+    }
+}
+
+fn read_config_lines(path: &PathBuf) -> Result<Vec<ConfigLine>, Error> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| Error::IoError(path.clone(), e))?;
+
+    Ok(content.lines().map(parse_config_line).collect())
+}
+
+/// Manages load order for OpenMW.
+///
+/// Unlike the Bethesda engines, OpenMW has no separate load order file and
+/// active plugins file: `openmw.cfg` lists plugins once, as ordered
+/// `content=` lines, and a plugin's presence there is what makes it active.
+/// `openmw.cfg` is also layered: a global config file provides a base
+/// configuration, and a user config file (read and written after it) can
+/// add to or override it. This backend reads every configured config file
+/// in order and writes the plugin load order back to the last one, leaving
+/// every other line in it untouched.
+#[derive(Clone, Debug)]
+pub struct OpenMWLoadOrder {
+    game_settings: GameSettings,
+    plugins: Vec<Plugin>,
+}
+
+impl OpenMWLoadOrder {
+    pub fn new(game_settings: GameSettings) -> Self {
+        Self {
+            game_settings,
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Reads the `content=` lines out of every configured openmw.cfg file,
+    /// in order. A plugin that's listed in more than one file (e.g. the
+    /// user config re-stating a plugin the global config already lists)
+    /// keeps the position it was first seen at.
+    fn read_content_lines(&self) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        let mut seen = HashSet::new();
+
+        for path in self.game_settings.openmw_config_paths() {
+            for line in read_config_lines(&path)? {
+                if let ConfigLine::Content(name) = line {
+                    if seen.insert(UniCase::new(name.clone())) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+
+        Ok(names)
+    }
+
+    /// Rewrites the last configured openmw.cfg file (the user config,
+    /// which is applied last and so takes precedence) so that its
+    /// `content=` lines match the active plugins in `self.plugins`, in
+    /// order; inactive plugins get no `content=` line. Every `data=` line
+    /// and every other line already in that file is preserved, in its
+    /// original position relative to the other preserved lines; the
+    /// `content=` lines are always written last.
+    fn write_content_lines(&self) -> Result<(), Error> {
+        let Some(path) = self.game_settings.openmw_config_paths().into_iter().last() else {
+            return Ok(());
+        };
+
+        let mut lines: Vec<ConfigLine> = read_config_lines(&path)?
+            .into_iter()
+            .filter(|line| !matches!(line, ConfigLine::Content(_)))
+            .collect();
+
+        lines.extend(
+            self.plugins
+                .iter()
+                .filter(|plugin| plugin.is_active())
+                .map(|plugin| ConfigLine::Content(plugin.name().to_string())),
+        );
+
+        let content: String = lines
+            .iter()
+            .map(config_line_to_string)
+            .map(|line| line + "\n")
+            .collect();
+
+        create_parent_dirs(&path)?;
+
+        fs::write(&path, content).map_err(|e| Error::IoError(path, e))
+    }
+}
+
+impl ReadableLoadOrderBase for OpenMWLoadOrder {
+    fn game_settings_base(&self) -> &GameSettings {
+        &self.game_settings
+    }
+
+    fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+}
+
+impl MutableLoadOrder for OpenMWLoadOrder {
+    fn plugins_mut(&mut self) -> &mut Vec<Plugin> {
+        &mut self.plugins
+    }
+
+    fn insert_position(&self, plugin: &Plugin) -> Option<usize> {
+        generic_insert_position(self.plugins(), plugin)
+    }
+}
+
+impl WritableLoadOrder for OpenMWLoadOrder {
+    fn game_settings_mut(&mut self) -> &mut GameSettings {
+        &mut self.game_settings
+    }
+
+    fn load(&mut self) -> Result<(), Error> {
+        self.plugins_mut().clear();
+
+        let content_names = self.read_content_lines()?;
+        let installed_names = self.find_plugins_sorted();
+
+        let mut seen = HashSet::new();
+        for name in content_names {
+            if seen.insert(UniCase::new(name.clone())) {
+                if let Ok(plugin) = Plugin::with_active(&name, &self.game_settings, true) {
+                    self.plugins.push(plugin);
+                }
+            }
+        }
+
+        for name in installed_names {
+            if seen.insert(UniCase::new(name.clone())) {
+                if let Ok(plugin) = Plugin::new(&name, &self.game_settings) {
+                    self.plugins.push(plugin);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites the `content=` lines of the user's openmw.cfg to match the
+    /// current load order, preserving every other line.
+    fn save(&mut self) -> Result<(), Error> {
+        self.write_content_lines()
+    }
+
+    fn add(&mut self, plugin_name: &str) -> Result<usize, Error> {
+        add(self, plugin_name)
+    }
+
+    fn remove(&mut self, plugin_name: &str) -> Result<(), Error> {
+        remove(self, plugin_name)
+    }
+
+    fn set_load_order(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+        self.replace_plugins(plugin_names)
+    }
+
+    fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error> {
+        self.move_or_insert_plugin_with_index(plugin_name, position)
+    }
+
+    /// OpenMW has a single list of plugins, so there's nothing for that
+    /// list to be inconsistent with.
+    fn is_self_consistent(&self) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    /// OpenMW has a single list of plugins, so there's nothing for that
+    /// list to be ambiguous with respect to.
+    fn is_ambiguous(&self) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    fn activate(&mut self, plugin_name: &str) -> Result<(), Error> {
+        activate(self, plugin_name)
+    }
+
+    fn deactivate(&mut self, plugin_name: &str) -> Result<(), Error> {
+        deactivate(self, plugin_name)
+    }
+
+    fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
+        set_active_plugins(self, active_plugin_names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use tempfile::tempdir;
+
+    use crate::enums::GameId;
+    use crate::load_order::tests::*;
+    use crate::tests::copy_to_test_dir;
+
+    fn write_config(path: &std::path::Path, content: &str) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        let mut file = fs::File::create(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn prepare(game_path: &std::path::Path) -> OpenMWLoadOrder {
+        let settings = game_settings_for_test(GameId::OpenMWMorrowind, game_path);
+
+        OpenMWLoadOrder::new(settings)
+    }
+
+    #[test]
+    fn read_content_lines_should_preserve_order_and_dedupe_across_config_files() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        let paths = load_order.game_settings().openmw_config_paths();
+        write_config(
+            &paths[0],
+            "data=\"/game/data\"\ncontent=Blank.esm\ncontent=Blank.esp\n",
+        );
+        write_config(
+            &paths[1],
+            "# user overrides\ncontent=Blank.esm\ncontent=Blank - Different.esp\n",
+        );
+
+        let names = load_order.read_content_lines().unwrap();
+
+        assert_eq!(
+            vec!["Blank.esm", "Blank.esp", "Blank - Different.esp"],
+            names
+        );
+    }
+
+    #[test]
+    fn write_content_lines_should_preserve_other_lines_and_rewrite_content_lines() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", load_order.game_settings());
+        copy_to_test_dir("Blank.esp", "Blank.esp", load_order.game_settings());
+
+        let paths = load_order.game_settings().openmw_config_paths();
+        write_config(
+            &paths[1],
+            "# a comment\ndata=\"/game/data\"\ncontent=Blank.esm\nfallback-archive=Morrowind.bsa\n",
+        );
+
+        load_order.plugins = vec![
+            Plugin::with_active("Blank.esm", load_order.game_settings(), true).unwrap(),
+            Plugin::with_active("Blank.esp", load_order.game_settings(), true).unwrap(),
+        ];
+
+        load_order.write_content_lines().unwrap();
+
+        let written = fs::read_to_string(&paths[1]).unwrap();
+
+        assert_eq!(
+            "# a comment\ndata=\"/game/data\"\nfallback-archive=Morrowind.bsa\ncontent=Blank.esm\ncontent=Blank.esp\n",
+            written
+        );
+    }
+
+    #[test]
+    fn write_content_lines_should_not_write_an_inactive_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", load_order.game_settings());
+        copy_to_test_dir("Blank.esp", "Blank.esp", load_order.game_settings());
+
+        let paths = load_order.game_settings().openmw_config_paths();
+
+        load_order.plugins = vec![
+            Plugin::with_active("Blank.esm", load_order.game_settings(), true).unwrap(),
+            Plugin::with_active("Blank.esp", load_order.game_settings(), false).unwrap(),
+        ];
+
+        load_order.write_content_lines().unwrap();
+
+        let written = fs::read_to_string(&paths[1]).unwrap();
+
+        assert_eq!("content=Blank.esm\n", written);
+    }
+}
@@ -16,16 +16,19 @@
  * You should have received a copy of the GNU General Public License
  * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
  */
-use std::collections::HashSet;
-use std::fs::File;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
 use std::io::{BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 
-use unicase::eq;
+use unicase::{eq, UniCase};
 
 use super::mutable::{
+    count_overlapping_records, find_conflicting_plugins, find_record_overrides,
     generic_insert_position, hoist_masters, load_active_plugins, plugin_line_mapper,
-    read_plugin_names, MutableLoadOrder,
+    read_plugin_names, sort_plugins_with_rules, validate_active_plugin_counts,
+    validate_light_plugins, validate_load_order, validate_medium_plugins, MutableLoadOrder,
+    PluginRecordConflicts, SortRule,
 };
 use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
 use super::strict_encode;
@@ -67,25 +70,208 @@ impl TextfileBasedLoadOrder {
 
     fn save_load_order(&self) -> Result<(), Error> {
         if let Some(file_path) = self.game_settings().load_order_file() {
-            create_parent_dirs(file_path)?;
+            write_file_atomically(file_path, |writer| {
+                for plugin_name in self.plugin_names() {
+                    writeln!(writer, "{}", plugin_name)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
 
-            let file = File::create(file_path)?;
-            let mut writer = BufWriter::new(file);
-            for plugin_name in self.plugin_names() {
-                writeln!(writer, "{}", plugin_name)?;
+    fn save_active_plugins(&self) -> Result<(), Error> {
+        write_file_atomically(self.game_settings().active_plugins_file(), |writer| {
+            for plugin_name in self.active_plugin_names() {
+                writer.write_all(&strict_encode(plugin_name)?)?;
+                writeln!(writer)?;
             }
+            Ok(())
+        })
+    }
+
+    /// Checks that `plugin_name`, if it's loaded, still has new records
+    /// within the object-index window its light/medium class reserves for
+    /// it. If it doesn't, the plugin is deactivated before the error is
+    /// returned, so a failed activation never leaves a plugin that can't
+    /// legally be active in that state.
+    fn validate_plugin_class(&mut self, plugin_name: &str) -> Result<(), Error> {
+        let game = self.game_settings().id();
+        let Some(plugin) = self
+            .plugins
+            .iter()
+            .find(|p| p.name_matches(plugin_name))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let result = validate_light_plugins(std::slice::from_ref(&plugin), game)
+            .and_then(|()| validate_medium_plugins(std::slice::from_ref(&plugin), game));
+
+        if result.is_err() {
+            deactivate(self, plugin_name)?;
         }
+
+        result
+    }
+
+    /// Reorders the load order according to `rules`, in addition to the
+    /// existing master-before-non-master constraint and the implicit
+    /// master dependency edges derived from each plugin's master list. See
+    /// [`SortRule`] for the supported rule kinds.
+    ///
+    /// The master/game-master invariants that `load` and `save` rely on are
+    /// re-established before this method returns, so the sorted load order
+    /// is always left in a valid state.
+    pub fn sort_load_order(&mut self, rules: &[SortRule]) -> Result<(), Error> {
+        let order = sort_plugins_with_rules(&self.plugins, rules)?;
+
+        let mut sorted_plugins = Vec::with_capacity(order.len());
+        for index in order {
+            sorted_plugins.push(self.plugins[index].clone());
+        }
+
+        let game_master = self.game_settings().master_file();
+        if let Some(index) = sorted_plugins.iter().position(|p| eq(p.name(), game_master)) {
+            let game_master_plugin = sorted_plugins.remove(index);
+            sorted_plugins.insert(0, game_master_plugin);
+        }
+
+        hoist_masters(&mut sorted_plugins)?;
+        validate_load_order(&sorted_plugins)?;
+
+        self.plugins = sorted_plugins;
+
         Ok(())
     }
 
-    fn save_active_plugins(&self) -> Result<(), Error> {
-        create_parent_dirs(self.game_settings().active_plugins_file())?;
+    /// Reports which pairs of active plugins touch at least one of the
+    /// same record, in load order with the later-loading, overriding
+    /// plugin of each pair listed second. See
+    /// [`overlapping_record_count`](Self::overlapping_record_count) for
+    /// the number of records a given pair has in common.
+    pub fn plugin_conflicts(&self) -> Result<Vec<(String, String)>, Error> {
+        let active_names: HashSet<String> = self.active_plugin_names().into_iter().collect();
+        let active_plugins: Vec<Plugin> = self
+            .plugins
+            .iter()
+            .filter(|p| active_names.contains(p.name()))
+            .cloned()
+            .collect();
+
+        find_conflicting_plugins(&active_plugins)
+    }
+
+    /// Counts how many records the plugins named `a` and `b` both touch.
+    pub fn overlapping_record_count(&self, a: &str, b: &str) -> Result<usize, Error> {
+        let index_a = self.index_of(a).ok_or_else(|| Error::PluginNotFound(a.to_string()))?;
+        let index_b = self.index_of(b).ok_or_else(|| Error::PluginNotFound(b.to_string()))?;
+
+        count_overlapping_records(&self.plugins[index_a], &self.plugins[index_b])
+    }
+
+    /// Reports, for each active plugin, which other active plugins it
+    /// overrides at least one record of and which other active plugins
+    /// override at least one of its own records, so that callers can warn
+    /// users about mods silently overriding each other's content. Unlike
+    /// [`plugin_conflicts`](Self::plugin_conflicts), which only reports
+    /// that two plugins touch the same record, this attributes each record
+    /// to the plugin that actually wins it in the current load order.
+    pub fn record_overrides(&self) -> Result<HashMap<String, PluginRecordConflicts>, Error> {
+        let active_names: HashSet<String> = self.active_plugin_names().into_iter().collect();
+        let active_plugins: Vec<Plugin> = self
+            .plugins
+            .iter()
+            .filter(|p| active_names.contains(p.name()))
+            .cloned()
+            .collect();
+
+        let conflicts = find_record_overrides(&active_plugins)?;
+
+        Ok(active_plugins
+            .iter()
+            .map(|p| p.name().to_string())
+            .zip(conflicts)
+            .collect())
+    }
+
+    /// Reconciles loadorder.txt and plugins.txt when they disagree, and
+    /// appends any installed plugins that are missing from the in-memory
+    /// load order. loadorder.txt is treated as authoritative for ordering,
+    /// while each plugin's active state is taken from plugins.txt where
+    /// it's listed there. Both files are then re-saved, so a subsequent
+    /// call to `is_self_consistent` returns `true` and `is_ambiguous`
+    /// returns `false`.
+    pub fn make_self_consistent(&mut self) -> Result<(), Error> {
+        if let SelfConsistency::Inconsistent = check_self_consistency(self.game_settings())? {
+            let load_order_names = self.read_from_load_order_file()?;
+            let active_names = self.read_from_active_plugins_file()?;
+
+            let active_set: HashSet<UniCase<String>> = active_names
+                .into_iter()
+                .map(|(name, _)| UniCase::new(trim_dot_ghost(&name).to_string()))
+                .collect();
+
+            let plugin_tuples: Vec<(String, bool)> = load_order_names
+                .into_iter()
+                .map(|(name, _)| {
+                    let is_active =
+                        active_set.contains(&UniCase::new(trim_dot_ghost(&name).to_string()));
+                    (name, is_active)
+                })
+                .collect();
+
+            self.plugins.clear();
+
+            let filenames = self.find_plugins_sorted();
+            self.load_unique_plugins(plugin_tuples, filenames);
+
+            hoist_masters(&mut self.plugins)?;
+        }
+
+        for filename in self.find_plugins_sorted() {
+            if self.index_of(&filename).is_none() {
+                self.add(&filename)?;
+            }
+        }
 
-        let file = File::create(self.game_settings().active_plugins_file())?;
-        let mut writer = BufWriter::new(file);
-        for plugin_name in self.active_plugin_names() {
-            writer.write_all(&strict_encode(plugin_name)?)?;
-            writeln!(writer)?;
+        self.save()
+    }
+
+    /// Rewrites loadorder.txt so that it lists every plugin named in either
+    /// loadorder.txt or plugins.txt, without discarding or reordering any
+    /// plugin the two files already agree on. A plugin that's only in
+    /// plugins.txt is inserted just after the nearest plugin that precedes
+    /// it in plugins.txt and is already in loadorder.txt, so the merged
+    /// order is consistent with the relative ordering both files agree on.
+    /// Unlike [`make_self_consistent`](Self::make_self_consistent), this
+    /// doesn't touch plugins.txt or the in-memory load order, and it's a
+    /// no-op (not just idempotent) when `is_self_consistent` is already
+    /// true.
+    pub fn normalise_load_order_file(&self) -> Result<(), Error> {
+        if let SelfConsistency::Inconsistent = check_self_consistency(self.game_settings())? {
+            let load_order_names: Vec<String> = self
+                .read_from_load_order_file()?
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            let active_names: Vec<String> = self
+                .read_from_active_plugins_file()?
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+
+            let merged = merge_ordered_with_extra(&load_order_names, &active_names);
+
+            if let Some(file_path) = self.game_settings().load_order_file() {
+                write_file_atomically(file_path, |writer| {
+                    for plugin_name in &merged {
+                        writeln!(writer, "{plugin_name}")?;
+                    }
+                    Ok(())
+                })?;
+            }
         }
 
         Ok(())
@@ -128,6 +314,8 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
     }
 
     fn load(&mut self) -> Result<(), Error> {
+        self.validate_additional_plugins_directories()?;
+
         self.plugins_mut().clear();
 
         let load_order_file_exists = self
@@ -156,9 +344,52 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
         Ok(())
     }
 
+    /// Writes loadorder.txt and plugins.txt atomically, so that a crash or
+    /// an error partway through never leaves either file truncated and
+    /// never leaves the pair in a state that `is_self_consistent` would
+    /// report as inconsistent. If plugins.txt fails to write after
+    /// loadorder.txt has already been replaced, the previous loadorder.txt
+    /// is restored from a backup copy before the error is returned.
     fn save(&mut self) -> Result<(), Error> {
-        self.save_load_order()?;
-        self.save_active_plugins()
+        self.validate_additional_plugins_directories()?;
+
+        let active_plugins: Vec<Plugin> = self
+            .plugins
+            .iter()
+            .filter(|p| p.is_active())
+            .cloned()
+            .collect();
+        validate_light_plugins(&active_plugins, self.game_settings().id())?;
+        validate_medium_plugins(&active_plugins, self.game_settings().id())?;
+        validate_active_plugin_counts(&self.plugins, self.game_settings().id())?;
+
+        let load_order_backup = match self.game_settings().load_order_file() {
+            Some(file_path) => backup_file(file_path)?,
+            None => FileBackup::DidNotExist,
+        };
+
+        if let Err(error) = self.save_load_order() {
+            // save_load_order writes atomically (temp file then rename), so
+            // a failure here never replaces loadorder.txt: the backup was
+            // never needed and is just cleaned up, not restored.
+            if let FileBackup::Existed(backup_path) = load_order_backup {
+                let _ = fs::remove_file(backup_path);
+            }
+            return Err(error);
+        }
+
+        if let Err(error) = self.save_active_plugins() {
+            if let Some(file_path) = self.game_settings().load_order_file() {
+                restore_file(load_order_backup, file_path)?;
+            }
+            return Err(error);
+        }
+
+        if let FileBackup::Existed(backup_path) = load_order_backup {
+            fs::remove_file(backup_path)?;
+        }
+
+        Ok(())
     }
 
     fn add(&mut self, plugin_name: &str) -> Result<usize, Error> {
@@ -232,7 +463,8 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
     }
 
     fn activate(&mut self, plugin_name: &str) -> Result<(), Error> {
-        activate(self, plugin_name)
+        activate(self, plugin_name)?;
+        self.validate_plugin_class(plugin_name)
     }
 
     fn deactivate(&mut self, plugin_name: &str) -> Result<(), Error> {
@@ -240,7 +472,22 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
     }
 
     fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
-        set_active_plugins(self, active_plugin_names)
+        set_active_plugins(self, active_plugin_names)?;
+
+        // Validate and, for any invalid plugin, deactivate every plugin in
+        // the batch before returning, instead of stopping at the first
+        // invalid one and leaving the rest marked active unvalidated.
+        let mut first_error = None;
+        for plugin_name in active_plugin_names {
+            if let Err(error) = self.validate_plugin_class(plugin_name) {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 }
 
@@ -260,6 +507,64 @@ where
     Ok(content.lines().filter_map(line_mapper).collect())
 }
 
+/// Writes to `file_path` by creating a sibling temporary file on the same
+/// filesystem, flushing and fsyncing it, then atomically renaming it over
+/// `file_path`. This ensures that readers never observe a partially
+/// written file, and that a crash during the write leaves the previous
+/// contents of `file_path` untouched.
+fn write_file_atomically<F>(file_path: &Path, write_contents: F) -> Result<(), Error>
+where
+    F: FnOnce(&mut BufWriter<File>) -> Result<(), Error>,
+{
+    create_parent_dirs(file_path)?;
+
+    let temp_path = sibling_path(file_path, ".tmp");
+
+    let file = File::create(&temp_path)?;
+    let mut writer = BufWriter::new(file);
+    write_contents(&mut writer)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    drop(writer);
+
+    fs::rename(&temp_path, file_path)?;
+
+    Ok(())
+}
+
+fn sibling_path(file_path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = file_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    file_path.with_file_name(file_name)
+}
+
+enum FileBackup {
+    Existed(PathBuf),
+    DidNotExist,
+}
+
+fn backup_file(file_path: &Path) -> Result<FileBackup, Error> {
+    if file_path.exists() {
+        let backup_path = sibling_path(file_path, ".bak");
+        fs::copy(file_path, &backup_path)?;
+        Ok(FileBackup::Existed(backup_path))
+    } else {
+        Ok(FileBackup::DidNotExist)
+    }
+}
+
+fn restore_file(backup: FileBackup, file_path: &Path) -> Result<(), Error> {
+    match backup {
+        FileBackup::Existed(backup_path) => fs::rename(&backup_path, file_path)?,
+        FileBackup::DidNotExist => {
+            if file_path.exists() {
+                fs::remove_file(file_path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 enum SelfConsistency {
     ConsistentNoLoadOrderFile,
     ConsistentOnlyLoadOrderFile(PathBuf),
@@ -319,6 +624,27 @@ fn plugin_names_match(name1: &str, name2: &str) -> bool {
     eq(trim_dot_ghost(name1), trim_dot_ghost(name2))
 }
 
+/// Merges `extra` into `base`, keeping `base`'s existing order unchanged
+/// and inserting each name from `extra` that's missing from `base` just
+/// after the last name from `extra` that was found in `base`, so the
+/// result respects the relative order `extra` and `base` already agree on.
+fn merge_ordered_with_extra(base: &[String], extra: &[String]) -> Vec<String> {
+    let mut merged = base.to_vec();
+    let mut insert_at = 0;
+
+    for name in extra {
+        match merged.iter().position(|n| plugin_names_match(n, name)) {
+            Some(existing_index) => insert_at = existing_index + 1,
+            None => {
+                merged.insert(insert_at, name.clone());
+                insert_at += 1;
+            }
+        }
+    }
+
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,7 +653,7 @@ mod tests {
     use crate::load_order::tests::*;
     use crate::tests::copy_to_test_dir;
     use filetime::{set_file_times, FileTime};
-    use std::fs::{remove_dir_all, File};
+    use std::fs::{read, remove_dir_all, File};
     use std::io::Write;
     use std::path::Path;
     use tempfile::tempdir;
@@ -474,6 +800,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_should_resolve_load_order_file_plugin_names_to_their_on_disk_casing() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let file_filenames = vec!["Skyrim.esm", "BLANK.ESM", "blank.esp"];
+        write_load_order_file(load_order.game_settings(), &file_filenames);
+
+        load_order.load().unwrap();
+
+        let expected_filenames = vec!["Skyrim.esm", "Blank.esm", "Blank.esp"];
+        assert_eq!(
+            &expected_filenames[..],
+            &load_order.plugin_names().as_slice()[..expected_filenames.len()]
+        );
+    }
+
     #[test]
     fn load_should_hoist_non_masters_that_masters_depend_on_to_load_before_their_dependents() {
         let tmp_dir = tempdir().unwrap();
@@ -742,6 +1085,50 @@ mod tests {
         assert_eq!(expected_filenames, load_order.plugin_names());
     }
 
+    #[test]
+    fn load_should_not_duplicate_a_plugin_that_is_ghosted_with_a_differently_cased_suffix() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        use std::fs::rename;
+
+        rename(
+            load_order
+                .game_settings()
+                .plugins_directory()
+                .join("Blank.esm"),
+            load_order
+                .game_settings()
+                .plugins_directory()
+                .join("Blank.ESM.GHOST"),
+        )
+        .unwrap();
+
+        let expected_filenames = vec![
+            "Skyrim.esm",
+            "Blank.esm",
+            "Blàñk.esp",
+            "Blank - Master Dependent.esp",
+            "Blank - Different.esp",
+            "Blank.esp",
+            "missing.esp",
+        ];
+        write_load_order_file(load_order.game_settings(), &expected_filenames);
+
+        load_order.load().unwrap();
+
+        let expected_filenames = vec![
+            load_order.game_settings().master_file(),
+            "Blank.esm",
+            "Blàñk.esp",
+            "Blank - Master Dependent.esp",
+            "Blank - Different.esp",
+            "Blank.esp",
+        ];
+
+        assert_eq!(expected_filenames, load_order.plugin_names());
+    }
+
     #[test]
     fn save_should_write_all_plugins_to_load_order_file() {
         let tmp_dir = tempdir().unwrap();
@@ -817,6 +1204,58 @@ mod tests {
         };
     }
 
+    #[test]
+    fn save_should_restore_load_order_file_if_writing_active_plugins_file_fails() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.save().unwrap();
+
+        let load_order_file = load_order.game_settings().load_order_file().unwrap();
+        let original_contents = read(load_order_file).unwrap();
+        let backup_path = PathBuf::from(format!("{}.bak", load_order_file.display()));
+
+        let filename = "Bl\u{0227}nk.esm";
+        copy_to_test_dir(
+            "Blank - Different.esm",
+            filename,
+            &load_order.game_settings(),
+        );
+        let mut plugin = Plugin::new(filename, &load_order.game_settings()).unwrap();
+        plugin.activate().unwrap();
+        load_order.plugins_mut().push(plugin);
+
+        assert!(load_order.save().is_err());
+
+        assert_eq!(original_contents, read(load_order_file).unwrap());
+        assert!(!backup_path.exists());
+    }
+
+    #[test]
+    fn save_should_not_leave_a_backup_file_behind_if_writing_the_load_order_file_fails() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.save().unwrap();
+
+        let load_order_file = load_order.game_settings().load_order_file().unwrap();
+        let original_contents = read(load_order_file).unwrap();
+        let backup_path = PathBuf::from(format!("{}.bak", load_order_file.display()));
+        let temp_path = PathBuf::from(format!("{}.tmp", load_order_file.display()));
+
+        // write_file_atomically creates this path as its temp file, so
+        // pre-creating it as a directory makes the write fail before
+        // loadorder.txt itself is ever touched.
+        fs::create_dir(&temp_path).unwrap();
+
+        assert!(load_order.save().is_err());
+
+        assert_eq!(original_contents, read(load_order_file).unwrap());
+        assert!(!backup_path.exists());
+
+        fs::remove_dir(&temp_path).unwrap();
+    }
+
     #[test]
     fn set_load_order_should_error_if_given_an_empty_list() {
         let tmp_dir = tempdir().unwrap();
@@ -883,6 +1322,31 @@ mod tests {
         assert!(load_order.set_load_order(&filenames).is_ok());
     }
 
+    #[test]
+    fn set_load_order_should_not_distinguish_between_differently_cased_ghosted_and_unghosted_filenames(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        copy_to_test_dir(
+            "Blank - Different.esm",
+            "ghosted.ESM.Ghost",
+            &load_order.game_settings(),
+        );
+
+        let filenames = vec![
+            "Skyrim.esm",
+            "Blank.esm",
+            "Ghosted.esm",
+            "Blank.esp",
+            "Blank - Master Dependent.esp",
+            "Blank - Different.esp",
+            "Blàñk.esp",
+        ];
+
+        assert!(load_order.set_load_order(&filenames).is_ok());
+    }
+
     #[test]
     fn set_load_order_should_not_insert_missing_plugins() {
         let tmp_dir = tempdir().unwrap();
@@ -948,6 +1412,51 @@ mod tests {
         assert_eq!(num_plugins + 1, load_order.plugins().len());
     }
 
+    #[test]
+    fn sort_load_order_should_apply_an_order_rule() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let rules = [SortRule::Order(
+            "Blank - Different.esp".to_string(),
+            "Blank.esp".to_string(),
+        )];
+
+        load_order.sort_load_order(&rules).unwrap();
+
+        assert!(
+            load_order.index_of("Blank - Different.esp").unwrap()
+                < load_order.index_of("Blank.esp").unwrap()
+        );
+    }
+
+    #[test]
+    fn sort_load_order_should_keep_the_game_master_file_first() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let rules = [SortRule::NearEnd("Skyrim.esm".to_string())];
+
+        load_order.sort_load_order(&rules).unwrap();
+
+        assert_eq!(Some(0), load_order.index_of("Skyrim.esm"));
+    }
+
+    #[test]
+    fn sort_load_order_should_error_if_the_rules_form_a_cycle() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let rules = [
+            SortRule::Order("Blank.esp".to_string(), "Blank - Different.esp".to_string()),
+            SortRule::Order("Blank - Different.esp".to_string(), "Blank.esp".to_string()),
+        ];
+
+        assert!(load_order.sort_load_order(&rules).is_err());
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
     #[test]
     fn is_self_consistent_should_return_true_when_no_load_order_file_exists() {
         let tmp_dir = tempdir().unwrap();
@@ -1183,4 +1692,129 @@ mod tests {
 
         assert!(!load_order.is_ambiguous().unwrap());
     }
+
+    #[test]
+    fn make_self_consistent_should_reorder_plugins_to_match_load_order_file_when_inconsistent() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let load_order_filenames = vec!["Skyrim.esm", "Blank - Different.esp", "Blank.esp"];
+        write_load_order_file(load_order.game_settings(), &load_order_filenames);
+        write_active_plugins_file(load_order.game_settings(), &["Blank.esp", "Skyrim.esm"]);
+
+        assert!(!load_order.is_self_consistent().unwrap());
+
+        load_order.make_self_consistent().unwrap();
+
+        assert!(load_order.is_self_consistent().unwrap());
+        let listed_plugin_names: Vec<&str> = load_order
+            .plugin_names()
+            .into_iter()
+            .filter(|n| load_order_filenames.contains(n))
+            .collect();
+        assert_eq!(load_order_filenames, listed_plugin_names);
+        assert!(load_order.is_active("Skyrim.esm"));
+        assert!(load_order.is_active("Blank.esp"));
+        assert!(!load_order.is_active("Blank - Different.esp"));
+    }
+
+    #[test]
+    fn make_self_consistent_should_append_installed_plugins_missing_from_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order
+            .plugins_mut()
+            .retain(|p| p.name() != "Blank - Different.esp");
+        load_order.save().unwrap();
+
+        assert!(load_order.index_of("Blank - Different.esp").is_none());
+
+        load_order.make_self_consistent().unwrap();
+
+        assert!(load_order.index_of("Blank - Different.esp").is_some());
+    }
+
+    #[test]
+    fn normalise_load_order_file_should_do_nothing_if_already_self_consistent() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let load_order_filenames = vec!["Skyrim.esm", "Blank.esp", "Blank - Different.esp"];
+        write_load_order_file(load_order.game_settings(), &load_order_filenames);
+        write_active_plugins_file(load_order.game_settings(), &["Skyrim.esm", "Blank.esp"]);
+
+        assert!(load_order.is_self_consistent().unwrap());
+
+        load_order.normalise_load_order_file().unwrap();
+
+        let names: Vec<String> = read_utf8_plugin_names(
+            load_order.game_settings().load_order_file().unwrap(),
+            plugin_line_mapper,
+        )
+        .unwrap();
+
+        assert_eq!(load_order_filenames, names);
+    }
+
+    #[test]
+    fn normalise_load_order_file_should_insert_plugins_missing_from_the_load_order_file() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        write_load_order_file(load_order.game_settings(), &["Skyrim.esm", "Blank.esp"]);
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["Skyrim.esm", "Blank - Different.esp", "Blank.esp"],
+        );
+
+        assert!(!load_order.is_self_consistent().unwrap());
+
+        load_order.normalise_load_order_file().unwrap();
+
+        let names: Vec<String> = read_utf8_plugin_names(
+            load_order.game_settings().load_order_file().unwrap(),
+            plugin_line_mapper,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                "Skyrim.esm".to_string(),
+                "Blank - Different.esp".to_string(),
+                "Blank.esp".to_string(),
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn normalise_load_order_file_should_be_idempotent() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        write_load_order_file(load_order.game_settings(), &["Skyrim.esm", "Blank.esp"]);
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["Skyrim.esm", "Blank - Different.esp", "Blank.esp"],
+        );
+
+        load_order.normalise_load_order_file().unwrap();
+
+        let names_after_first_run: Vec<String> = read_utf8_plugin_names(
+            load_order.game_settings().load_order_file().unwrap(),
+            plugin_line_mapper,
+        )
+        .unwrap();
+
+        load_order.normalise_load_order_file().unwrap();
+
+        let names_after_second_run: Vec<String> = read_utf8_plugin_names(
+            load_order.game_settings().load_order_file().unwrap(),
+            plugin_line_mapper,
+        )
+        .unwrap();
+
+        assert_eq!(names_after_first_run, names_after_second_run);
+    }
 }